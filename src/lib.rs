@@ -5,9 +5,11 @@
 // 5. remove the pdf and divided rtf files
 
 use anyhow::Result;
+pub use pdf::combine::{combine_bytes, combine_read};
 use pdf::{combine::PDFCombiner, convert::PDFConverter};
 use probe::probe;
-use report::Report;
+pub use probe::{probe_bytes, probe_read, FootnotePlacement, Probe};
+pub use report::{Finding, Kind, Report};
 use rtf_divider::RTFDivider;
 use std::{
     fs,
@@ -1,6 +1,7 @@
 use std::{
     collections::{BTreeMap, HashMap},
     fs,
+    io::{Cursor, Read, Seek, Write},
     path::{Path, PathBuf},
 };
 
@@ -53,12 +54,45 @@ impl PDFCombiner {
 }
 
 fn combine_one_output(source: &[PathBuf], dest: &Path) -> anyhow::Result<()> {
-    let mut document = Document::with_version("1.7");
     let mut documents = vec![];
     for f in source {
         let doc = Document::load(f)?;
         documents.push(doc);
     }
+    if let Some(mut document) = combine_documents(documents) {
+        document.save(dest)?;
+    }
+    Ok(())
+}
+
+/// combine pdf parts held in memory in the given order, None when no part has a page tree
+pub fn combine_bytes(parts: &[&[u8]]) -> anyhow::Result<Option<Vec<u8>>> {
+    let mut combined = vec![];
+    let written = combine_read(parts.iter().map(Cursor::new), &mut combined)?;
+    Ok(if written { Some(combined) } else { None })
+}
+
+/// combine pdf parts read from any source in the given order and write the result to dest,
+/// returns false when no part has a page tree and nothing was written
+pub fn combine_read<R: Read + Seek, W: Write>(
+    parts: impl IntoIterator<Item = R>,
+    dest: &mut W,
+) -> anyhow::Result<bool> {
+    let mut documents = vec![];
+    for part in parts {
+        documents.push(Document::load_from(part)?);
+    }
+    match combine_documents(documents) {
+        Some(mut document) => {
+            document.save_to(dest)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+fn combine_documents(documents: Vec<Document>) -> Option<Document> {
+    let mut document = Document::with_version("1.7");
 
     // Define a starting max_id (will be used as start index for object_ids)
     let mut max_id = 1;
@@ -140,16 +174,13 @@ fn combine_one_output(source: &[PathBuf], dest: &Path) -> anyhow::Result<()> {
     }
 
     // If no "Pages" object found abort
-    if pages_object.is_none() {
-        // println!("Pages root not found.");
-        return Ok(());
-    }
+    let pages_object = pages_object?;
 
     // Iterate over all "Page" objects and collect into the parent "Pages" created before
     for (object_id, object) in documents_pages.iter() {
         if let Ok(dictionary) = object.as_dict() {
             let mut dictionary = dictionary.clone();
-            dictionary.set("Parent", pages_object.as_ref().unwrap().0);
+            dictionary.set("Parent", pages_object.0);
 
             document
                 .objects
@@ -158,14 +189,7 @@ fn combine_one_output(source: &[PathBuf], dest: &Path) -> anyhow::Result<()> {
     }
 
     // If no "Catalog" found abort
-    if catalog_object.is_none() {
-        // println!("Catalog root not found.");
-
-        return Ok(());
-    }
-
-    let catalog_object = catalog_object.unwrap();
-    let pages_object = pages_object.unwrap();
+    let catalog_object = catalog_object?;
 
     // Build a new "Pages" with updated fields
     if let Ok(dictionary) = pages_object.1.as_dict() {
@@ -220,13 +244,14 @@ fn combine_one_output(source: &[PathBuf], dest: &Path) -> anyhow::Result<()> {
     }
 
     document.compress();
-    document.save(dest)?;
-    Ok(())
+    Some(document)
 }
 
 #[cfg(test)]
 mod test_pdf_combine {
     use super::*;
+    use crate::pdf::reader::test_util::{pdf, show};
+
     #[test]
     fn pdf_combine_test() {
         let dir = Path::new(r"D:\Studies\ak112\303\stats\CSR\product\output\.temp");
@@ -234,4 +259,18 @@ mod test_pdf_combine {
         let combiner = PDFCombiner::new(dir).unwrap();
         combiner.combine_output(dest).unwrap();
     }
+
+    fn single_page(text: &str) -> Vec<u8> {
+        pdf(&[show(&[(72f64, 700f64, text)])])
+    }
+
+    #[test]
+    fn combine_bytes_test() {
+        let first = single_page("first");
+        let second = single_page("second");
+        let combined = combine_bytes(&[&first, &second]).unwrap().unwrap();
+        let doc = Document::load_mem(&combined).unwrap();
+        assert_eq!(doc.get_pages().len(), 2);
+        assert_eq!(combine_bytes(&[]).unwrap(), None);
+    }
 }
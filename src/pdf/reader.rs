@@ -1,194 +1,1207 @@
-use std::{cell::RefCell, collections::HashMap, io::Read, ops::Sub, path::Path};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    io::{Read, Seek},
+    path::Path,
+};
 
-use flate2::read::ZlibDecoder;
-use lopdf::{Dictionary, Document};
+use operation::{Operand, Operation};
+use tj::TjItem;
+use tm::Matrix;
 
+use lopdf::{Dictionary, Document, Object, ObjectId, Stream};
+
+mod encoding;
+mod font;
+mod glyph;
+mod layout;
 mod mapper;
+mod operation;
+mod page;
+mod path;
+mod table;
+#[cfg(test)]
+pub mod test_util;
 mod tj;
 mod tm;
+mod truetype;
+
+pub use layout::TextRun;
+pub use page::{Geometry, Page};
+pub use path::Rule;
+#[cfg(test)]
+pub use path::Segment;
+pub use table::{closing_rule, Cell};
 
 const RESOURCES: &[u8] = "Resources".as_bytes();
 const FONT: &[u8] = "Font".as_bytes();
+const PARENT: &[u8] = "Parent".as_bytes();
+// guard against cyclic /Parent references in broken page trees
+const MAX_TREE_DEPTH: usize = 32;
 const TO_UNICODE: &[u8] = "ToUnicode".as_bytes();
-const SPACE: u8 = b' ';
+const SUBTYPE: &[u8] = "Subtype".as_bytes();
+const TYPE0: &[u8] = "Type0".as_bytes();
+const ENCODING: &[u8] = "Encoding".as_bytes();
+const BASE_ENCODING: &[u8] = "BaseEncoding".as_bytes();
+const DIFFERENCES: &[u8] = "Differences".as_bytes();
+const DESCENDANT_FONTS: &[u8] = "DescendantFonts".as_bytes();
+const FONT_DESCRIPTOR: &[u8] = "FontDescriptor".as_bytes();
+const FONT_FILE2: &[u8] = "FontFile2".as_bytes();
+const FONT_FILE3: &[u8] = "FontFile3".as_bytes();
+const OPEN_TYPE: &[u8] = "OpenType".as_bytes();
+const CID_TO_GID_MAP: &[u8] = "CIDToGIDMap".as_bytes();
+const USE_CMAP: &[u8] = "UseCMap".as_bytes();
+const FIRST_CHAR: &[u8] = "FirstChar".as_bytes();
+const WIDTHS: &[u8] = "Widths".as_bytes();
+const MISSING_WIDTH: &[u8] = "MissingWidth".as_bytes();
+const W: &[u8] = "W".as_bytes();
+const DW: &[u8] = "DW".as_bytes();
+const SPACE: char = ' ';
 const CONTENTS: &[u8] = "Contents".as_bytes();
-const TM: &[u8] = "Tm\r".as_bytes();
-const TF: &[u8] = "Tf\r".as_bytes();
-const TJ_WORD: &[u8] = "TJ\r".as_bytes();
-const TJ_WPS: &[u8] = "Tj\n".as_bytes();
-const NEWLINE: u8 = b'\n';
-const RETURN: u8 = b'\r';
+const XOBJECT: &[u8] = "XObject".as_bytes();
+const IMAGE: &[u8] = "Image".as_bytes();
+const FORM: &[u8] = "Form".as_bytes();
+const MATRIX: &[u8] = "Matrix".as_bytes();
+const WIDTH: &[u8] = "Width".as_bytes();
+const HEIGHT: &[u8] = "Height".as_bytes();
+const MEDIA_BOX: &[u8] = "MediaBox".as_bytes();
+const CROP_BOX: &[u8] = "CropBox".as_bytes();
+const ROTATE: &[u8] = "Rotate".as_bytes();
+const DEVICE_GRAY: &str = "DeviceGray";
+// width assumed for glyphs of fonts which could not be resolved, in thousandths of text space
+const UNKNOWN_FONT_WIDTH: f64 = 500f64;
+// a gap wider than this share of the space glyph is read as a word break
+const SPACE_GAP_RATIO: f64 = 0.3;
 
 pub struct PDFReader {
     doc: Document,
-    decode_map: RefCell<HashMap<String, HashMap<String, String>>>,
-    pages: RefCell<Vec<Vec<String>>>,
+    /// fonts keyed by object id, resource names are only meaningful inside a single page
+    fonts: RefCell<HashMap<ObjectId, font::Font>>,
+    /// page objects in document order, pages are only decoded when asked for
+    page_ids: Vec<ObjectId>,
 }
 
 impl PDFReader {
     pub fn new(file: &Path) -> anyhow::Result<PDFReader> {
-        let doc = Document::load(file)?;
-        let decode_map = RefCell::new(HashMap::new());
-        let pages = RefCell::new(vec![]);
-        let reader = PDFReader {
+        Ok(PDFReader::from_document(Document::load(file)?))
+    }
+
+    /// read a pdf held in memory
+    pub fn from_bytes(data: &[u8]) -> anyhow::Result<PDFReader> {
+        Ok(PDFReader::from_document(Document::load_mem(data)?))
+    }
+
+    /// read a pdf from any source such as a file unpacked from an archive
+    pub fn from_read<R: Read + Seek>(source: R) -> anyhow::Result<PDFReader> {
+        Ok(PDFReader::from_document(Document::load_from(source)?))
+    }
+
+    fn from_document(doc: Document) -> PDFReader {
+        let page_ids = doc.get_pages().into_values().collect();
+        PDFReader {
             doc,
-            decode_map,
-            pages,
-        };
-        for page_obj_id in reader.doc.page_iter() {
-            let page_obj = reader.doc.get_object(page_obj_id)?;
-            for dict in page_obj.as_dict().iter() {
-                let dict = dict.as_hashmap();
-                if let Some(resource) = dict.get(RESOURCES) {
-                    let resource = resource.as_dict()?;
-                    let font = resource.get(FONT)?.as_dict()?;
-                    reader.insert_decode_map(font)?;
-                }
-                if let Some(content_id) = dict.get(CONTENTS) {
-                    let content_stream = &reader
-                        .doc
-                        .get_object(content_id.as_reference()?)?
-                        .as_stream()?
-                        .content;
-                    let content_stream = decode(&content_stream)?;
-                    reader.build_page_content(&content_stream)?;
-                }
+            fonts: RefCell::new(HashMap::new()),
+            page_ids,
+        }
+    }
+
+    /// resolve the resource names of a category such as /Font of a page to object ids, resources
+    /// inherited from parent /Pages nodes are used unless the page (or a nearer node) defines the
+    /// same name
+    fn page_resources(
+        &self,
+        page: &Dictionary,
+        category: &[u8],
+    ) -> anyhow::Result<HashMap<String, ObjectId>> {
+        let mut names = HashMap::new();
+        let mut node = Some(page);
+        let mut depth = 0;
+        while let Some(dict) = node {
+            if depth > MAX_TREE_DEPTH {
+                break;
             }
+            self.resource_names(dict, category, &mut names)?;
+            node = match dict.get(PARENT) {
+                Ok(parent) => Some(self.doc.dereference(parent)?.1.as_dict()?),
+                Err(_) => None,
+            };
+            depth += 1;
         }
-        Ok(reader)
+        Ok(names)
     }
 
-    fn insert_decode_map(&self, font: &Dictionary) -> anyhow::Result<()> {
-        for (font_name, object) in font.iter() {
-            let font_name = String::from_utf8(font_name.to_owned())?;
-            if self.decode_map.borrow().contains_key(&font_name) {
-                continue;
+    /// an inheritable attribute of a page, looked up through the parent /Pages nodes
+    fn inherited<'a>(
+        &'a self,
+        page: &'a Dictionary,
+        key: &[u8],
+    ) -> anyhow::Result<Option<&'a Object>> {
+        let mut node = page;
+        for _ in 0..MAX_TREE_DEPTH {
+            if let Ok(value) = node.get(key) {
+                return Ok(Some(self.doc.dereference(value)?.1));
             }
-            let font = self.doc.get_object(object.as_reference()?)?.as_dict()?;
-            if let Ok(unicode) = font.get(TO_UNICODE) {
-                let stream_content = &self
-                    .doc
-                    .get_object(unicode.as_reference()?)?
-                    .as_stream()?
-                    .content;
-                let stream_content = decode(&stream_content)?;
-                let code_map = mapper::build_unicode_map(&stream_content);
-                if let Some(code_map) = code_map {
-                    self.decode_map.borrow_mut().insert(font_name, code_map);
+            node = match node.get(PARENT) {
+                Ok(parent) => self.doc.dereference(parent)?.1.as_dict()?,
+                Err(_) => break,
+            };
+        }
+        Ok(None)
+    }
+
+    fn page_geometry(&self, page: &Dictionary) -> anyhow::Result<Geometry> {
+        let rectangle = |key| -> anyhow::Result<Option<[f64; 4]>> {
+            let values = match self.inherited(page, key)? {
+                Some(Object::Array(values)) if values.len() == 4 => values,
+                _ => return Ok(None),
+            };
+            let mut rectangle = [0f64; 4];
+            for (i, value) in values.iter().enumerate() {
+                rectangle[i] = self.number(value).unwrap_or(0f64);
+            }
+            Ok(Some(rectangle))
+        };
+        let rotate = self
+            .inherited(page, ROTATE)?
+            .and_then(|rotate| rotate.as_i64().ok())
+            .unwrap_or(0);
+        Ok(Geometry::new(
+            rectangle(MEDIA_BOX)?,
+            rectangle(CROP_BOX)?,
+            rotate,
+        ))
+    }
+
+    /// add the names of a category in the /Resources of a page or form, names already present
+    /// are kept
+    fn resource_names(
+        &self,
+        dict: &Dictionary,
+        category: &[u8],
+        names: &mut HashMap<String, ObjectId>,
+    ) -> anyhow::Result<()> {
+        if let Ok(resource) = dict.get(RESOURCES) {
+            let resource = self.doc.dereference(resource)?.1.as_dict()?;
+            if let Ok(entries) = resource.get(category) {
+                let entries = self.doc.dereference(entries)?.1.as_dict()?;
+                for (name, object) in entries.iter() {
+                    let name = String::from_utf8(name.to_owned())?;
+                    if names.contains_key(&name) {
+                        continue;
+                    }
+                    // resources defined inline have no id to be keyed by, they are skipped like before
+                    if let Object::Reference(id) = object {
+                        names.insert(name, *id);
+                    }
                 }
             }
         }
         Ok(())
     }
 
-    fn build_page_content(&self, data: &[u8]) -> anyhow::Result<()> {
-        let page = self.build_content(data)?;
-        self.pages.borrow_mut().push(page);
+    fn insert_fonts(&self, fonts: &HashMap<String, ObjectId>) -> anyhow::Result<()> {
+        for font_id in fonts.values() {
+            if self.fonts.borrow().contains_key(font_id) {
+                continue;
+            }
+            // a font which cannot be read is decoded with the standard encoding rather than
+            // failing the whole document
+            let font = self.load_font(*font_id).unwrap_or_else(|_| {
+                font::Font::new(
+                    None,
+                    Some(encoding::SimpleEncoding::new(encoding::STANDARD_ENCODING)),
+                )
+            });
+            self.fonts.borrow_mut().insert(*font_id, font);
+        }
         Ok(())
     }
 
-    pub fn build_content(&self, source: &[u8]) -> anyhow::Result<Vec<String>> {
-        let mut rows = vec![];
-        let mut row = String::new();
-        let mut row_start = 0;
-        let mut row_number = 0f64;
-        let mut font_type = String::new();
-        for (i, c) in source.iter().enumerate() {
-            if NEWLINE.eq(c) || RETURN.eq(c) {
-                if i > 3 {
-                    if let Some(mark) = source.get(i - TJ_WORD.len() + 1..i + 1) {
-                        if TJ_WORD.eq(mark) || TJ_WPS.eq(mark) {
-                            // handle contents generated by office word
-                            let content = source.get(row_start..i - TJ_WORD.len() + 1);
-                            let text: tj::Text = tj::handle_tj(content.unwrap());
-                            match text {
-                                tj::Text::ASCII(text) => {
-                                    row.push_str(&text);
-                                }
-                                tj::Text::UNICODE(text) => {
-                                    if let Some(decode_map) =
-                                        self.decode_map.borrow().get(&font_type)
-                                    {
-                                        let mut i = 0;
-                                        while let Some(word) = text.get(i..i + 4) {
-                                            if let Some(word) = decode_map.get(word) {
-                                                row.push_str(&unicode_to_u8(word));
-                                            }
-                                            i = i + 4;
-                                        }
-                                    }
-                                }
-                            }
-                        } else if TM.eq(mark) {
-                            // handle posistion information
-                            let content = source.get(row_start..i - TJ_WORD.len() + 1);
-                            let current_row = tm::handle_tm(content.unwrap())[5];
-                            let sub = row_number.sub(current_row);
-                            if sub > 1f64 || sub < -1f64 {
-                                rows.push(row.clone());
-                                row.clear();
-                                row_number = current_row;
-                            }
-                        } else if TF.eq(mark) {
-                            let content = source.get(row_start..i - TF.len() + 1);
-                            let mut i = 0;
-                            while let Some(c) = content.unwrap().get(i) {
-                                if SPACE.eq(c) {
-                                    break;
-                                }
-                                i += 1;
+    fn load_font(&self, font_id: ObjectId) -> anyhow::Result<font::Font> {
+        let font = self.doc.get_object(font_id)?.as_dict()?;
+        // a name such as /Identity-H in place of the stream maps nothing, a broken stream is
+        // left to the encoding or the glyph fallback
+        let to_unicode = match font.get(TO_UNICODE) {
+            Ok(Object::Reference(unicode)) => self
+                .load_unicode_map(*unicode, 0)
+                .unwrap_or(None)
+                .filter(|m| !m.is_empty()),
+            _ => None,
+        };
+        let simple = font
+            .get(SUBTYPE)
+            .and_then(Object::as_name)
+            .map_or(true, |subtype| subtype.ne(TYPE0));
+        if simple {
+            let encoding = self
+                .simple_encoding(font)
+                .unwrap_or_else(|_| encoding::SimpleEncoding::new(encoding::STANDARD_ENCODING));
+            let (widths, default_width) = self
+                .simple_widths(font)
+                .unwrap_or_else(|_| (HashMap::new(), UNKNOWN_FONT_WIDTH));
+            return Ok(
+                font::Font::new(to_unicode, Some(encoding)).set_widths(widths, default_width)
+            );
+        }
+        // a broken font program only costs the fallback, not the whole document
+        let glyph_fallback = self.glyph_fallback(font).unwrap_or(None);
+        let (widths, default_width) = self
+            .cid_widths(font)
+            .unwrap_or_else(|_| (HashMap::new(), 1000f64));
+        Ok(font::Font::new(to_unicode, None)
+            .set_glyph_fallback(glyph_fallback)
+            .set_widths(widths, default_width))
+    }
+
+    /// /Widths of a simple font starting at /FirstChar, codes outside use /MissingWidth
+    fn simple_widths(&self, font: &Dictionary) -> anyhow::Result<(HashMap<u32, f64>, f64)> {
+        let mut widths = HashMap::new();
+        let array = match font.get(WIDTHS) {
+            Ok(array) => self.doc.dereference(array)?.1.as_array()?,
+            // the standard 14 fonts may come without widths
+            Err(_) => return Ok((widths, UNKNOWN_FONT_WIDTH)),
+        };
+        let first_char = font.get(FIRST_CHAR).and_then(Object::as_i64).unwrap_or(0);
+        for (i, width) in array.iter().enumerate() {
+            if let Some(width) = self.number(width) {
+                widths.insert((first_char.max(0) as usize + i) as u32, width);
+            }
+        }
+        let missing_width = match font.get(FONT_DESCRIPTOR) {
+            Ok(descriptor) => self
+                .doc
+                .dereference(descriptor)?
+                .1
+                .as_dict()?
+                .get(MISSING_WIDTH)
+                .ok()
+                .and_then(|width| self.number(width)),
+            Err(_) => None,
+        };
+        Ok((widths, missing_width.unwrap_or(0f64)))
+    }
+
+    /// /W of the descendant font of a composite font, entries are either `c [w1 w2 ..]` or
+    /// `c_first c_last w`, cids outside use /DW
+    fn cid_widths(&self, font: &Dictionary) -> anyhow::Result<(HashMap<u32, f64>, f64)> {
+        let mut widths = HashMap::new();
+        let cid_font = match font.get(DESCENDANT_FONTS) {
+            Ok(descendants) => match self.doc.dereference(descendants)?.1.as_array()?.first() {
+                Some(cid_font) => self.doc.dereference(cid_font)?.1.as_dict()?,
+                None => return Ok((widths, 1000f64)),
+            },
+            Err(_) => return Ok((widths, 1000f64)),
+        };
+        let default_width = cid_font
+            .get(DW)
+            .ok()
+            .and_then(|width| self.number(width))
+            .unwrap_or(1000f64);
+        let array = match cid_font.get(W) {
+            Ok(array) => self.doc.dereference(array)?.1.as_array()?,
+            Err(_) => return Ok((widths, default_width)),
+        };
+        let mut i = 0;
+        while let Some(first) = array.get(i).and_then(|cid| self.number(cid)) {
+            let first = first as u32;
+            match array.get(i + 1).map(|item| self.doc.dereference(item)) {
+                Some(Ok((_, Object::Array(list)))) => {
+                    for (offset, width) in list.iter().enumerate() {
+                        if let Some(width) = self.number(width) {
+                            widths.insert(first + offset as u32, width);
+                        }
+                    }
+                    i += 2;
+                }
+                Some(Ok((_, last))) => {
+                    let last = self.number(last).unwrap_or(0f64) as u32;
+                    let width = array.get(i + 2).and_then(|width| self.number(width));
+                    if let Some(width) = width {
+                        if last >= first && last - first <= u16::MAX as u32 {
+                            for cid in first..=last {
+                                widths.insert(cid, width);
                             }
-                            font_type =
-                                String::from_utf8_lossy(content.unwrap().get(1..i).unwrap())
-                                    .to_string();
                         }
                     }
+                    i += 3;
+                }
+                _ => break,
+            }
+        }
+        Ok((widths, default_width))
+    }
+
+    fn number(&self, object: &Object) -> Option<f64> {
+        match self.doc.dereference(object).ok()?.1 {
+            Object::Integer(n) => Some(*n as f64),
+            Object::Real(n) => Some(*n as f64),
+            _ => None,
+        }
+    }
+
+    /// cid to unicode map of a composite font recovered from the cmap and post tables of its
+    /// embedded TrueType/OpenType program, used when ToUnicode is missing or incomplete, a bare
+    /// CFF program (/FontFile3 of subtype /CIDFontType0C) is not read: it is CID-keyed, so its
+    /// charset maps glyphs to CIDs instead of glyph names and it holds nothing to recover unicode
+    /// from
+    fn glyph_fallback(&self, font: &Dictionary) -> anyhow::Result<Option<HashMap<u32, String>>> {
+        let descendants = match font.get(DESCENDANT_FONTS) {
+            Ok(descendants) => self.doc.dereference(descendants)?.1.as_array()?,
+            Err(_) => return Ok(None),
+        };
+        let cid_font = match descendants.first() {
+            Some(cid_font) => self.doc.dereference(cid_font)?.1.as_dict()?,
+            None => return Ok(None),
+        };
+        let descriptor = match cid_font.get(FONT_DESCRIPTOR) {
+            Ok(descriptor) => self.doc.dereference(descriptor)?.1.as_dict()?,
+            Err(_) => return Ok(None),
+        };
+        let font_file = if let Ok(file) = descriptor.get(FONT_FILE2) {
+            self.doc.dereference(file)?.1.as_stream()?
+        } else if let Ok(file) = descriptor.get(FONT_FILE3) {
+            let file = self.doc.dereference(file)?.1.as_stream()?;
+            // bare CFF programs carry no cmap table and, being CID-keyed, no glyph names either
+            if !file
+                .dict
+                .get(SUBTYPE)
+                .and_then(Object::as_name)
+                .is_ok_and(|subtype| subtype.eq(OPEN_TYPE))
+            {
+                return Ok(None);
+            }
+            file
+        } else {
+            return Ok(None);
+        };
+        let glyphs = match truetype::glyph_unicode_map(&stream_data(font_file)) {
+            Some(glyphs) => glyphs,
+            None => return Ok(None),
+        };
+        let cid_to_gid = match cid_font.get(CID_TO_GID_MAP) {
+            Ok(map) => match self.doc.dereference(map)?.1 {
+                Object::Stream(stream) => Some(stream_data(stream)),
+                // /Identity
+                _ => None,
+            },
+            Err(_) => None,
+        };
+        let map = match cid_to_gid {
+            Some(cid_to_gid) => cid_to_gid
+                .chunks(2)
+                .enumerate()
+                .filter_map(|(cid, gid)| {
+                    let gid = u16::from_be_bytes([gid[0], *gid.get(1).unwrap_or(&0)]);
+                    glyphs.get(&gid).map(|text| (cid as u32, text.clone()))
+                })
+                .collect(),
+            None => glyphs
+                .into_iter()
+                .map(|(gid, text)| (gid as u32, text))
+                .collect(),
+        };
+        Ok(Some(map))
+    }
+
+    /// the /Encoding of a simple font, either a standard encoding name or a dictionary with an
+    /// optional /BaseEncoding and /Differences
+    fn simple_encoding(&self, font: &Dictionary) -> anyhow::Result<encoding::SimpleEncoding> {
+        let encoding = match font.get(ENCODING) {
+            Ok(encoding) => self.doc.dereference(encoding)?.1,
+            Err(_) => return Ok(encoding::SimpleEncoding::new(encoding::STANDARD_ENCODING)),
+        };
+        match encoding {
+            Object::Name(name) => Ok(encoding::SimpleEncoding::new(&String::from_utf8_lossy(
+                name,
+            ))),
+            Object::Dictionary(dict) => {
+                let base = dict
+                    .get(BASE_ENCODING)
+                    .and_then(Object::as_name)
+                    .map(|name| String::from_utf8_lossy(name).to_string())
+                    .unwrap_or(encoding::STANDARD_ENCODING.into());
+                let mut simple = encoding::SimpleEncoding::new(&base);
+                if let Ok(differences) = dict.get(DIFFERENCES) {
+                    let differences = self.doc.dereference(differences)?.1.as_array()?;
+                    simple.apply_differences(differences);
+                }
+                Ok(simple)
+            }
+            _ => Ok(encoding::SimpleEncoding::new(encoding::STANDARD_ENCODING)),
+        }
+    }
+
+    /// parse a ToUnicode stream, a parent cmap given as stream in /UseCMap is merged in
+    fn load_unicode_map(
+        &self,
+        stream_id: ObjectId,
+        depth: usize,
+    ) -> anyhow::Result<Option<mapper::UnicodeMap>> {
+        let stream = self.doc.get_object(stream_id)?.as_stream()?;
+        let stream_content = stream_data(stream);
+        let mut code_map = mapper::build_unicode_map(&stream_content);
+        if let (Some(code_map), Ok(Object::Reference(parent_id))) =
+            (code_map.as_mut(), stream.dict.get(USE_CMAP))
+        {
+            if depth < MAX_TREE_DEPTH {
+                if let Some(parent) = self.load_unicode_map(*parent_id, depth + 1)? {
+                    code_map.merge_parent(&parent);
+                }
+            }
+        }
+        Ok(code_map)
+    }
+
+    fn build_page(
+        &self,
+        data: &[u8],
+        resources: &Resources,
+        geometry: Geometry,
+    ) -> anyhow::Result<Page> {
+        let mut page = Page {
+            geometry,
+            ..page::Page::default()
+        };
+        // text outside the visible box is clipped
+        let graphics = GraphicsState {
+            ctm: geometry.matrix(),
+            clip: [0f64, 0f64, geometry.width(), geometry.height()],
+            ..GraphicsState::default()
+        };
+        self.read_content(data, resources, graphics, &mut page, 0)?;
+        Ok(page)
+    }
+
+    /// the text shown by each text operator of a content stream together with its position, the
+    /// lines painted by path operators and the placed images, form xobjects are read in place
+    fn read_content(
+        &self,
+        source: &[u8],
+        resources: &Resources,
+        mut graphics: GraphicsState,
+        page: &mut Page,
+        depth: usize,
+    ) -> anyhow::Result<()> {
+        let fonts = &resources.fonts;
+        let mut state = TextState::default();
+        let mut saved = vec![];
+        let mut path = path::PathBuilder::default();
+        // bounds of a clipping path, it takes effect once the path is painted
+        let mut clip = None;
+        for operation in operation::parse_operations(source) {
+            let items = match operation.operator.as_str() {
+                "Tj" | "TJ" => tj::handle_tj(&operation.operands),
+                "'" => {
+                    state.next_line();
+                    tj::handle_tj(&operation.operands)
+                }
+                "\"" => {
+                    state.word_spacing = operation.number(0);
+                    state.char_spacing = operation.number(1);
+                    state.next_line();
+                    tj::handle_tj(operation.operands.get(2..).unwrap_or_default())
+                }
+                "Tf" => {
+                    state.font_id = operation
+                        .operands
+                        .first()
+                        .and_then(|name| name.as_name())
+                        .and_then(|name| fonts.get(name))
+                        .copied();
+                    state.size = operation.number(1);
+                    continue;
+                }
+                // the text state is part of the graphics state and saved along with it
+                "q" => {
+                    saved.push((graphics, state));
+                    continue;
+                }
+                "Q" => {
+                    (graphics, state) = saved.pop().unwrap_or_default();
+                    continue;
+                }
+                "cm" => {
+                    graphics.ctm = tm::multiply(&tm::handle_tm(&operation.operands), &graphics.ctm);
+                    continue;
+                }
+                "w" => {
+                    graphics.line_width = operation.number(0);
+                    continue;
+                }
+                "g" | "rg" | "k" => {
+                    graphics.fill_white = is_white(&operation.operands, true);
+                    continue;
+                }
+                "G" | "RG" | "K" => {
+                    graphics.stroke_white = is_white(&operation.operands, true);
+                    continue;
+                }
+                "cs" | "CS" => {
+                    // the initial color of a color space is black
+                    let gray =
+                        operation.operands.first().and_then(|o| o.as_name()) == Some(DEVICE_GRAY);
+                    if operation.operator == "cs" {
+                        (graphics.fill_white, graphics.fill_gray) = (false, gray);
+                    } else {
+                        (graphics.stroke_white, graphics.stroke_gray) = (false, gray);
+                    }
+                    continue;
+                }
+                "sc" | "scn" => {
+                    graphics.fill_white = is_white(&operation.operands, graphics.fill_gray);
+                    continue;
+                }
+                "SC" | "SCN" => {
+                    graphics.stroke_white = is_white(&operation.operands, graphics.stroke_gray);
+                    continue;
+                }
+                "W" | "W*" => {
+                    clip = path.bounds();
+                    continue;
+                }
+                "Do" => {
+                    if let Some(name) = operation.operands.first().and_then(|o| o.as_name()) {
+                        self.show_xobject(name, resources, graphics, page, depth)?;
+                    }
+                    continue;
+                }
+                "BI" => {
+                    let size = |keys: [&str; 2]| {
+                        operation
+                            .operands
+                            .first()
+                            .and_then(|dict| dict.get(keys[0]).or(dict.get(keys[1])))
+                            .and_then(Operand::as_number)
+                            .unwrap_or(0f64)
+                    };
+                    let (width, height) = (size(["W", "Width"]), size(["H", "Height"]));
+                    page.images
+                        .push(page::Image::new(None, width, height, &graphics.ctm));
+                    continue;
+                }
+                operator => {
+                    if path.apply(&operation, &graphics.ctm) {
+                        continue;
+                    }
+                    let line_width = graphics.line_width * tm::scale(&graphics.ctm);
+                    if let Some(segments) = path.paint(operator, line_width) {
+                        page.segments.extend(segments);
+                        if let Some(clip) = clip.take() {
+                            graphics.clip = intersect(&graphics.clip, &clip);
+                        }
+                        continue;
+                    }
+                    state.apply(&operation);
+                    continue;
+                }
+            };
+
+            let loaded = self.fonts.borrow();
+            let font = state.font_id.and_then(|id| loaded.get(&id));
+            let space_gap = SPACE_GAP_RATIO
+                * font.map_or(UNKNOWN_FONT_WIDTH / 2f64, |f| f.space_width())
+                / 1000f64;
+            let trm = tm::multiply(&state.tm, &graphics.ctm);
+            let (x, y) = (trm[4], trm[5]);
+            let angle = trm[1].atan2(trm[0]).to_degrees();
+            let white = match state.render_mode {
+                1 | 5 => graphics.stroke_white,
+                _ => graphics.fill_white,
+            };
+            let shown = !matches!(state.render_mode, 3 | 7) && !white;
+            let mut text = String::new();
+            for item in items {
+                let (bytes, literal) = match item {
+                    TjItem::Offset(offset) => {
+                        // negative adjustments move the next glyph to the right
+                        if -offset / 1000f64 > space_gap {
+                            push_space(&mut text);
+                        }
+                        state.advance(-offset / 1000f64 * state.size * state.scaling);
+                        continue;
+                    }
+                    TjItem::Literal(bytes) => (bytes, true),
+                    TjItem::Hex(bytes) => (bytes, false),
+                };
+                if let Some(utf16) = tj::utf16_text(&bytes) {
+                    let glyphs = utf16.chars().count();
+                    text.push_str(&utf16);
+                    state.advance_glyphs(glyphs as f64 * UNKNOWN_FONT_WIDTH, glyphs, 0);
+                } else if let Some(font) = font {
+                    text.push_str(&font.decode(&bytes));
+                    let codes = font.codes(&bytes);
+                    let width = codes.iter().map(|code| font.width(code)).sum::<f64>();
+                    let spaces = codes.iter().filter(|code| **code == [SPACE as u8]).count();
+                    state.advance_glyphs(width, codes.len(), spaces);
+                } else {
+                    if literal {
+                        text.push_str(&String::from_utf8_lossy(&bytes));
+                    }
+                    let width = bytes.len() as f64 * UNKNOWN_FONT_WIDTH;
+                    state.advance_glyphs(width, bytes.len(), 0);
+                }
+            }
+            let trm = tm::multiply(&state.tm, &graphics.ctm);
+            let size = state.size * tm::scale(&trm);
+            page.runs.push(layout::TextRun {
+                text,
+                x,
+                y,
+                end_x: trm[4],
+                end_y: trm[5],
+                size,
+                space_gap: space_gap * size,
+                angle,
+                visible: shown
+                    && (contains(&graphics.clip, x, y) || contains(&graphics.clip, trm[4], trm[5])),
+            });
+        }
+        Ok(())
+    }
+
+    /// paint the xobject of a resource name, images are listed with their placement and forms
+    /// are read with their own resources
+    fn show_xobject(
+        &self,
+        name: &str,
+        resources: &Resources,
+        graphics: GraphicsState,
+        page: &mut Page,
+        depth: usize,
+    ) -> anyhow::Result<()> {
+        let stream = match resources.xobjects.get(name) {
+            Some(id) => self.doc.get_object(*id)?.as_stream()?,
+            None => return Ok(()),
+        };
+        let dict = &stream.dict;
+        match dict.get(SUBTYPE).and_then(Object::as_name) {
+            Ok(IMAGE) => {
+                let size = |key| dict.get(key).ok().and_then(|n| self.number(n));
+                let (width, height) = (size(WIDTH), size(HEIGHT));
+                page.images.push(page::Image::new(
+                    Some(name.to_string()),
+                    width.unwrap_or(0f64),
+                    height.unwrap_or(0f64),
+                    &graphics.ctm,
+                ));
+            }
+            Ok(FORM) if depth < MAX_TREE_DEPTH => {
+                let mut matrix = tm::IDENTITY;
+                if let Ok(values) = dict.get(MATRIX).and_then(Object::as_array) {
+                    for (i, value) in values.iter().take(6).enumerate() {
+                        matrix[i] = self.number(value).unwrap_or(0f64);
+                    }
+                }
+                // names missing from the form resources are looked up in the enclosing ones
+                let mut form = Resources::default();
+                self.resource_names(dict, FONT, &mut form.fonts)?;
+                self.resource_names(dict, XOBJECT, &mut form.xobjects)?;
+                for (name, id) in resources.fonts.iter() {
+                    form.fonts.entry(name.clone()).or_insert(*id);
                 }
-                row_start = i + 1;
+                for (name, id) in resources.xobjects.iter() {
+                    form.xobjects.entry(name.clone()).or_insert(*id);
+                }
+                self.insert_fonts(&form.fonts)?;
+                let graphics = GraphicsState {
+                    ctm: tm::multiply(&matrix, &graphics.ctm),
+                    ..graphics
+                };
+                let data = stream_data(stream);
+                self.read_content(&data, &form, graphics, page, depth + 1)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.page_ids.len()
+    }
+
+    /// decode the page at a zero based index
+    pub fn page(&self, index: usize) -> anyhow::Result<Page> {
+        let page_id = match self.page_ids.get(index) {
+            Some(page_id) => *page_id,
+            None => anyhow::bail!("page {} out of range of {}", index + 1, self.page_count()),
+        };
+        let dict = self.doc.get_object(page_id)?.as_dict()?;
+        let resources = Resources {
+            fonts: self.page_resources(dict, FONT)?,
+            xobjects: self.page_resources(dict, XOBJECT)?,
+        };
+        self.insert_fonts(&resources.fonts)?;
+        let geometry = self.page_geometry(dict)?;
+        let content_stream = match dict.get(CONTENTS) {
+            Ok(contents) => self.page_content(contents)?,
+            // a page without content is read as a blank page
+            Err(_) => vec![],
+        };
+        self.build_page(&content_stream, &resources, geometry)
+    }
+
+    /// the content of a page given as a stream or as an array of streams, which are joined as if
+    /// they were one
+    fn page_content(&self, contents: &Object) -> anyhow::Result<Vec<u8>> {
+        match self.doc.dereference(contents)?.1 {
+            Object::Stream(stream) => Ok(stream_data(stream)),
+            Object::Array(streams) => {
+                let mut content = vec![];
+                for stream in streams {
+                    content.extend(stream_data(self.doc.dereference(stream)?.1.as_stream()?));
+                    // an operator may not run on from one stream into the next
+                    content.push(b'\n');
+                }
+                Ok(content)
+            }
+            _ => anyhow::bail!("page contents are neither a stream nor an array of streams"),
+        }
+    }
+
+    /// decode the pages one at a time as the iterator is advanced
+    pub fn pages(&self) -> impl Iterator<Item = anyhow::Result<Page>> + '_ {
+        (0..self.page_count()).map(|index| self.page(index))
+    }
+}
+
+/// Resource names of a page or form xobject mapped to object ids
+#[derive(Default)]
+struct Resources {
+    fonts: HashMap<String, ObjectId>,
+    xobjects: HashMap<String, ObjectId>,
+}
+
+/// Graphics state parameters saved by q and restored by Q together with the text state
+#[derive(Clone, Copy)]
+struct GraphicsState {
+    ctm: Matrix,
+    line_width: f64,
+    fill_white: bool,
+    stroke_white: bool,
+    /// single component colors are gray levels rather than tints
+    fill_gray: bool,
+    stroke_gray: bool,
+    /// bounds of the clipping path as `[llx lly urx ury]` on the upright page
+    clip: [f64; 4],
+}
+
+impl Default for GraphicsState {
+    fn default() -> Self {
+        GraphicsState {
+            ctm: tm::IDENTITY,
+            line_width: 1f64,
+            fill_white: false,
+            stroke_white: false,
+            fill_gray: true,
+            stroke_gray: true,
+            clip: [
+                f64::NEG_INFINITY,
+                f64::NEG_INFINITY,
+                f64::INFINITY,
+                f64::INFINITY,
+            ],
+        }
+    }
+}
+
+/// whether the operands of a color operator give white, one operand is only read as gray level
+/// when gray is set since tints of separations run the other way
+fn is_white(operands: &[Operand], gray: bool) -> bool {
+    let values = operands
+        .iter()
+        .map(Operand::as_number)
+        .collect::<Option<Vec<_>>>();
+    match values.as_deref() {
+        Some([level]) => gray && *level >= 1f64,
+        Some([r, g, b]) => [r, g, b].iter().all(|c| **c >= 1f64),
+        Some([c, m, y, k]) => [c, m, y, k].iter().all(|c| **c <= 0f64),
+        _ => false,
+    }
+}
+
+fn intersect(a: &[f64; 4], b: &[f64; 4]) -> [f64; 4] {
+    [
+        a[0].max(b[0]),
+        a[1].max(b[1]),
+        a[2].min(b[2]),
+        a[3].min(b[3]),
+    ]
+}
+
+fn contains(area: &[f64; 4], x: f64, y: f64) -> bool {
+    (area[0]..=area[2]).contains(&x) && (area[1]..=area[3]).contains(&y)
+}
+
+/// Text state parameters and matrices of a text object
+#[derive(Clone, Copy)]
+struct TextState {
+    font_id: Option<ObjectId>,
+    size: f64,
+    char_spacing: f64,
+    word_spacing: f64,
+    /// horizontal scaling as a factor
+    scaling: f64,
+    leading: f64,
+    render_mode: i64,
+    tm: Matrix,
+    tlm: Matrix,
+}
+
+impl Default for TextState {
+    fn default() -> Self {
+        TextState {
+            font_id: None,
+            size: 0f64,
+            char_spacing: 0f64,
+            word_spacing: 0f64,
+            scaling: 1f64,
+            leading: 0f64,
+            render_mode: 0,
+            tm: tm::IDENTITY,
+            tlm: tm::IDENTITY,
+        }
+    }
+}
+
+impl TextState {
+    fn apply(&mut self, operation: &Operation) {
+        match operation.operator.as_str() {
+            "BT" => {
+                self.tm = tm::IDENTITY;
+                self.tlm = tm::IDENTITY;
+            }
+            "Tc" => self.char_spacing = operation.number(0),
+            "Tw" => self.word_spacing = operation.number(0),
+            "Tz" => self.scaling = operation.number(0) / 100f64,
+            "TL" => self.leading = operation.number(0),
+            "Tr" => self.render_mode = operation.number(0) as i64,
+            "Td" => self.move_line(operation.number(0), operation.number(1)),
+            "TD" => {
+                self.leading = -operation.number(1);
+                self.move_line(operation.number(0), operation.number(1));
+            }
+            "T*" => self.next_line(),
+            "Tm" => {
+                self.tlm = tm::handle_tm(&operation.operands);
+                self.tm = self.tlm;
             }
+            _ => {}
         }
-        Ok(rows)
     }
 
-    pub fn content(&self) -> Vec<Vec<String>> {
-        self.pages.borrow().to_owned()
+    fn move_line(&mut self, tx: f64, ty: f64) {
+        self.tlm = tm::multiply(&tm::translate(tx, ty), &self.tlm);
+        self.tm = self.tlm;
+    }
+
+    fn next_line(&mut self) {
+        self.move_line(0f64, -self.leading);
+    }
+
+    /// move the text matrix by tx in unscaled text space
+    fn advance(&mut self, tx: f64) {
+        self.tm = tm::multiply(&tm::translate(tx, 0f64), &self.tm);
+    }
+
+    /// move the text matrix behind shown glyphs, width is in thousandths of text space
+    fn advance_glyphs(&mut self, width: f64, glyphs: usize, spaces: usize) {
+        let tx = width / 1000f64 * self.size
+            + self.char_spacing * glyphs as f64
+            + self.word_spacing * spaces as f64;
+        self.advance(tx * self.scaling);
     }
 }
 
-fn decode(data: &[u8]) -> anyhow::Result<Vec<u8>> {
-    let mut buf = vec![];
-    let mut e = ZlibDecoder::new(data);
-    e.read_to_end(&mut buf)?;
-    Ok(buf)
+/// add a word break unless the row is empty or already ends with whitespace
+fn push_space(row: &mut String) {
+    if !row.is_empty() && !row.ends_with(char::is_whitespace) {
+        row.push(SPACE);
+    }
 }
 
-fn unicode_to_u8(source: &String) -> String {
-    let hex = u16::from_str_radix(source, 16).unwrap();
-    let r = char::decode_utf16(vec![hex])
-        .map(|r| r.unwrap())
-        .collect::<Vec<_>>();
-    r.iter().collect::<String>()
+/// content of a stream decoded through its filters, a stream without filter is read as is
+fn stream_data(stream: &Stream) -> Vec<u8> {
+    stream
+        .decompressed_content()
+        .unwrap_or_else(|_| stream.content.clone())
 }
 
 #[cfg(test)]
 mod pdf_reader_test {
     use super::*;
 
+    /// a reader with a winansi font of 500 wide glyphs as F1
+    fn win_ansi_reader() -> (PDFReader, Resources) {
+        let font_id = (1, 0);
+        let font = font::Font::new(
+            None,
+            Some(encoding::SimpleEncoding::new(encoding::WIN_ANSI_ENCODING)),
+        )
+        .set_widths(HashMap::new(), 500f64);
+        let reader = PDFReader {
+            doc: Document::with_version("1.7"),
+            fonts: RefCell::new(HashMap::from([(font_id, font)])),
+            page_ids: vec![],
+        };
+        let resources = Resources {
+            fonts: HashMap::from([("F1".to_string(), font_id)]),
+            ..Resources::default()
+        };
+        (reader, resources)
+    }
+
     #[test]
     fn read_test() {
         let p = Path::new(r"D:\Studies\ak112\303\stats\CSR\product\output\.temp\wps-cn.pdf");
         let r = PDFReader::new(p).unwrap();
-        let content = r.content();
+        let content = r
+            .pages()
+            .map(|page| page.unwrap().rows(false))
+            .collect::<Vec<_>>();
         assert_eq!(1, content.len());
         let p = Path::new(r"D:\Studies\ak112\303\stats\CSR\product\output\.temp\word-cn.pdf");
         let r = PDFReader::new(p).unwrap();
-        let content = r.content();
+        let content = r
+            .pages()
+            .map(|page| page.unwrap().rows(false))
+            .collect::<Vec<_>>();
         assert_eq!(17, content.len());
         let p = Path::new(
             r"D:\Studies\ak112\303\stats\CSR\product\output\bk\f-14-02-01-04-inve-pfs-for-fas.pdf",
         );
         let r = PDFReader::new(p).unwrap();
-        let content = r.content();
+        let content = r
+            .pages()
+            .map(|page| page.unwrap().rows(false))
+            .collect::<Vec<_>>();
         assert_eq!(1, content.len())
     }
+
+    #[test]
+    fn build_content_test() {
+        let (reader, resources) = win_ansi_reader();
+        let content = "BT\r/F1 10 Tf\r1 0 0 1 72 700 Tm\r[(AKESO)-280(Inc)2(.)]TJ\r\
+1 0 0 1 200 700 Tm\r(Page)Tj\r1 0 0 1 72 680 Tm\r[(p)-6(r)5(o)]TJ\rET";
+        let rows = reader
+            .build_page(content.as_bytes(), &resources, Geometry::default())
+            .unwrap()
+            .rows(false);
+        assert_eq!(rows, vec!["AKESO Inc. Page", "pro"]);
+    }
+
+    #[test]
+    fn page_resources_test() {
+        use lopdf::dictionary;
+        let mut doc = Document::with_version("1.7");
+        let parent_font = doc.add_object(dictionary! { "Type" => "Font" });
+        let page_font = doc.add_object(dictionary! { "Type" => "Font" });
+        let other_font = doc.add_object(dictionary! { "Type" => "Font" });
+        let pages = doc.add_object(dictionary! {
+            "Type" => "Pages",
+            "Resources" => dictionary! {
+                "Font" => dictionary! { "F1" => parent_font, "F2" => other_font },
+            },
+        });
+        let inherited = dictionary! { "Type" => "Page", "Parent" => pages };
+        let own = dictionary! {
+            "Type" => "Page",
+            "Parent" => pages,
+            "Resources" => dictionary! { "Font" => dictionary! { "F1" => page_font } },
+        };
+        let reader = PDFReader {
+            doc,
+            fonts: RefCell::new(HashMap::new()),
+            page_ids: vec![],
+        };
+
+        let fonts = reader.page_resources(&inherited, FONT).unwrap();
+        assert_eq!(fonts.get("F1"), Some(&parent_font));
+        let fonts = reader.page_resources(&own, FONT).unwrap();
+        assert_eq!(fonts.get("F1"), Some(&page_font));
+        assert_eq!(fonts.get("F2"), Some(&other_font));
+    }
+
+    #[test]
+    fn show_xobject_test() {
+        use lopdf::dictionary;
+        let mut doc = Document::with_version("1.7");
+        let image = doc.add_object(Stream::new(
+            dictionary! { "Subtype" => "Image", "Width" => 40, "Height" => 30 },
+            vec![],
+        ));
+        let form = doc.add_object(Stream::new(
+            dictionary! {
+                "Subtype" => "Form",
+                "Matrix" => vec![1.into(), 0.into(), 0.into(), 1.into(), 10.into(), 0.into()],
+                "Resources" => dictionary! { "XObject" => dictionary! { "Im2" => image } },
+            },
+            b"q 100 0 0 50 0 0 cm /Im2 Do Q".to_vec(),
+        ));
+        let reader = PDFReader {
+            doc,
+            fonts: RefCell::new(HashMap::new()),
+            page_ids: vec![],
+        };
+        let resources = Resources {
+            xobjects: HashMap::from([("Im1".to_string(), image), ("Fm1".to_string(), form)]),
+            ..Resources::default()
+        };
+        let content = "q 200 0 0 150 72 500 cm /Im1 Do Q\r\
+q 1 0 0 1 0 100 cm /Fm1 Do Q\rBI /W 2 /H 1 /CS /G /BPC 8 ID \x00\x00 EI";
+        let page = reader
+            .build_page(content.as_bytes(), &resources, Geometry::default())
+            .unwrap();
+        let images = page.images;
+        let placement = images
+            .iter()
+            .map(|i| (i.name.as_deref(), i.x, i.y, i.end_x, i.end_y))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            placement,
+            vec![
+                (Some("Im1"), 72f64, 500f64, 272f64, 650f64),
+                (Some("Im2"), 10f64, 100f64, 110f64, 150f64),
+                (None, 0f64, 0f64, 1f64, 1f64),
+            ]
+        );
+        assert_eq!((images[0].width, images[0].height), (40f64, 30f64));
+        assert_eq!((images[2].width, images[2].height), (2f64, 1f64));
+    }
+
+    #[test]
+    fn rotated_page_test() {
+        let (reader, resources) = win_ansi_reader();
+        // a landscape listing on a portrait page turned by /Rotate 90
+        let content = "BT /F1 10 Tf 0 1 -1 0 100 72 Tm (Subject)Tj 0 1 -1 0 100 200 Tm (Age)Tj \
+0 1 -1 0 120 72 Tm (01001)Tj ET";
+        let geometry = Geometry::new(None, None, 90);
+        let page = reader
+            .build_page(content.as_bytes(), &resources, geometry)
+            .unwrap();
+        assert_eq!(page.rows(false), vec!["Subject Age", "01001"]);
+        let runs = &page.runs;
+        assert_eq!((runs[0].x, runs[0].y, runs[0].size), (72f64, 512f64, 10f64));
+    }
+
+    #[test]
+    fn hidden_text_test() {
+        let (reader, resources) = win_ansi_reader();
+        let content = "BT /F1 10 Tf 1 0 0 1 72 700 Tm (Table)Tj ET\r\
+BT /F1 10 Tf 3 Tr 1 0 0 1 72 680 Tm (ocr)Tj 0 Tr ET\r\
+q 1 g BT /F1 10 Tf 1 0 0 1 72 660 Tm (bookmark)Tj ET Q\r\
+/CS0 cs 1 1 1 scn BT /F1 10 Tf 1 0 0 1 72 640 Tm (white)Tj ET 0 g\r\
+BT /F1 10 Tf 1 0 0 1 72 900 Tm (outside)Tj ET\r\
+q 0 0 100 100 re W n BT /F1 10 Tf 1 0 0 1 200 620 Tm (clipped)Tj ET Q\r\
+BT /F1 10 Tf 1 0 0 1 72 600 Tm (Page)Tj ET";
+        let page = reader
+            .build_page(content.as_bytes(), &resources, Geometry::default())
+            .unwrap();
+        assert_eq!(page.rows(false), vec!["Table", "Page"]);
+        let visible = page.runs.iter().map(|run| run.visible).collect::<Vec<_>>();
+        assert_eq!(visible, vec![true, false, false, false, false, false, true]);
+    }
+
+    #[test]
+    fn saved_text_state_test() {
+        let (reader, resources) = win_ansi_reader();
+        // render mode and font set inside q...Q are undone by Q
+        let content = "BT /F1 10 Tf 1 0 0 1 72 700 Tm (Table)Tj ET\r\
+q BT 3 Tr /F1 12 Tf 1 0 0 1 72 680 Tm (ocr)Tj ET Q\r\
+BT 1 0 0 1 72 660 Tm (AKESO)Tj ET";
+        let page = reader
+            .build_page(content.as_bytes(), &resources, Geometry::default())
+            .unwrap();
+        assert_eq!(page.rows(false), vec!["Table", "AKESO"]);
+        assert_eq!((page.runs[2].visible, page.runs[2].size), (true, 10f64));
+    }
+
+    #[test]
+    fn load_font_test() {
+        use lopdf::dictionary;
+        let mut doc = Document::with_version("1.7");
+        let cmap = "1 begincodespacerange <00> <FF> endcodespacerange\n\
+1 beginbfchar <41> <0042> endbfchar";
+        let to_unicode = doc.add_object(Stream::new(dictionary! {}, cmap.as_bytes().to_vec()));
+        let plain = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "TrueType",
+            "ToUnicode" => to_unicode,
+        });
+        let named = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type0",
+            "ToUnicode" => "Identity-H",
+        });
+        let broken = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "Widths" => "none",
+            "Encoding" => 3,
+        });
+        let reader = PDFReader {
+            doc,
+            fonts: RefCell::new(HashMap::new()),
+            page_ids: vec![],
+        };
+        // an uncompressed cmap is read as is
+        let font = reader.load_font(plain).unwrap();
+        assert_eq!(font.decode(b"A"), "B");
+        assert!(reader.load_font(named).is_ok());
+        assert_eq!(reader.load_font(broken).unwrap().decode(b"A"), "A");
+        // an object which is no font at all does not stop the others from loading
+        let fonts = HashMap::from([("F1".to_string(), plain), ("F2".to_string(), to_unicode)]);
+        reader.insert_fonts(&fonts).unwrap();
+        assert_eq!(reader.fonts.borrow().len(), 2);
+    }
+
+    #[test]
+    fn contents_array_test() {
+        use lopdf::dictionary;
+        let first = "BT 1 0 0 1 72 700 Tm (first)Tj ET".to_string();
+        let mut doc = test_util::document(&[first]);
+        // padded so that the second part is worth compressing
+        let second = format!("BT 1 0 0 1 72 680 Tm (second)Tj ET{}", " ".repeat(200));
+        let mut second = Stream::new(dictionary! {}, second.into_bytes());
+        second.compress().unwrap();
+        assert!(second.dict.get(b"Filter").is_ok());
+        let second = doc.add_object(second);
+        let page = doc.get_dictionary_mut(doc.get_pages()[&1]).unwrap();
+        let first = page.get(CONTENTS).unwrap().clone();
+        page.set("Contents", vec![first, second.into()]);
+        let reader = PDFReader::from_document(doc);
+        assert_eq!(reader.page(0).unwrap().rows(false), vec!["first", "second"]);
+    }
+
+    #[test]
+    fn pages_test() {
+        let contents =
+            ["(first)Tj", "(second)Tj", ""].map(|text| format!("BT 1 0 0 1 72 700 Tm {} ET", text));
+        let mut doc = test_util::document(&contents);
+        let landscape = doc.get_pages()[&3];
+        let media_box = vec![0.into(), 0.into(), 792.into(), 612.into()];
+        doc.get_dictionary_mut(landscape)
+            .unwrap()
+            .set("MediaBox", media_box);
+        let mut data = vec![];
+        doc.save_to(&mut data).unwrap();
+        let reader = PDFReader::from_read(std::io::Cursor::new(data)).unwrap();
+
+        assert_eq!(reader.page_count(), 3);
+        assert_eq!(reader.page(1).unwrap().rows(false), vec!["second"]);
+        let page = reader.page(2).unwrap();
+        assert!(page.runs.is_empty());
+        assert!(page.geometry.width() > page.geometry.height());
+        assert!(reader.page(3).is_err());
+        // pages are decoded as the iterator is advanced, so a search can stop early
+        let first = reader
+            .pages()
+            .map(|page| page.unwrap().rows(false))
+            .find(|rows| rows.is_empty());
+        assert_eq!(first, Some(vec![]));
+        let rows = reader
+            .pages()
+            .map(|page| page.unwrap().rows(false))
+            .collect::<Vec<_>>();
+        assert_eq!(rows, vec![vec!["first"], vec!["second"], vec![]]);
+    }
 }
@@ -0,0 +1,146 @@
+use lopdf::{Document, Object};
+
+use super::glyph::glyph_to_unicode;
+
+pub const STANDARD_ENCODING: &str = "StandardEncoding";
+pub const WIN_ANSI_ENCODING: &str = "WinAnsiEncoding";
+pub const MAC_ROMAN_ENCODING: &str = "MacRomanEncoding";
+pub const PDF_DOC_ENCODING: &str = "PDFDocEncoding";
+
+/// code points of PDFDocEncoding which differ from latin-1
+const PDF_DOC_DIFFERENCES: &[(u8, char)] = &[
+    (0x18, '\u{02D8}'),
+    (0x19, '\u{02C7}'),
+    (0x1A, '\u{02C6}'),
+    (0x1B, '\u{02D9}'),
+    (0x1C, '\u{02DD}'),
+    (0x1D, '\u{02DB}'),
+    (0x1E, '\u{02DA}'),
+    (0x1F, '\u{02DC}'),
+    (0x80, '\u{2022}'),
+    (0x81, '\u{2020}'),
+    (0x82, '\u{2021}'),
+    (0x83, '\u{2026}'),
+    (0x84, '\u{2014}'),
+    (0x85, '\u{2013}'),
+    (0x86, '\u{0192}'),
+    (0x87, '\u{2044}'),
+    (0x88, '\u{2039}'),
+    (0x89, '\u{203A}'),
+    (0x8A, '\u{2212}'),
+    (0x8B, '\u{2030}'),
+    (0x8C, '\u{201E}'),
+    (0x8D, '\u{201C}'),
+    (0x8E, '\u{201D}'),
+    (0x8F, '\u{2018}'),
+    (0x90, '\u{2019}'),
+    (0x91, '\u{201A}'),
+    (0x92, '\u{2122}'),
+    (0x93, '\u{FB01}'),
+    (0x94, '\u{FB02}'),
+    (0x95, '\u{0141}'),
+    (0x96, '\u{0152}'),
+    (0x97, '\u{0160}'),
+    (0x98, '\u{0178}'),
+    (0x99, '\u{017D}'),
+    (0x9A, '\u{0131}'),
+    (0x9B, '\u{0142}'),
+    (0x9C, '\u{0153}'),
+    (0x9D, '\u{0161}'),
+    (0x9E, '\u{017E}'),
+    (0xA0, '\u{20AC}'),
+];
+
+/// Single byte encoding of a simple font, built from a standard encoding and /Differences
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimpleEncoding {
+    table: Vec<Option<String>>,
+}
+
+impl SimpleEncoding {
+    /// create from the name of a standard encoding, unknown names fall back to StandardEncoding
+    pub fn new(base: &str) -> SimpleEncoding {
+        let table = match base {
+            PDF_DOC_ENCODING => (0..=255u8)
+                .map(|code| {
+                    if let Some((_, c)) = PDF_DOC_DIFFERENCES.iter().find(|(b, _)| *b == code) {
+                        return Some(c.to_string());
+                    }
+                    match code {
+                        0x09 | 0x0A | 0x0D | 0x20..=0x7E | 0xA1..=0xAC | 0xAE..=0xFF => {
+                            Some((code as char).to_string())
+                        }
+                        _ => None,
+                    }
+                })
+                .collect(),
+            WIN_ANSI_ENCODING | MAC_ROMAN_ENCODING => lopdf_table(base),
+            _ => lopdf_table(STANDARD_ENCODING),
+        };
+        SimpleEncoding { table }
+    }
+
+    /// apply a /Differences array like `[24 /breve /caron 39 /quotesingle]`
+    pub fn apply_differences(&mut self, differences: &[Object]) {
+        let mut code = 0usize;
+        for item in differences {
+            match item {
+                Object::Integer(n) => code = (*n).max(0) as usize,
+                Object::Name(name) => {
+                    if let Some(slot) = self.table.get_mut(code) {
+                        *slot = glyph_to_unicode(&String::from_utf8_lossy(name));
+                    }
+                    code += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    pub fn get(&self, code: u8) -> Option<&str> {
+        self.table.get(code as usize).and_then(|c| c.as_deref())
+    }
+
+    pub fn decode(&self, source: &[u8]) -> String {
+        source.iter().filter_map(|code| self.get(*code)).collect()
+    }
+}
+
+fn lopdf_table(encoding: &str) -> Vec<Option<String>> {
+    (0..=255u8)
+        .map(|code| {
+            let text = Document::decode_text(Some(encoding), &[code]);
+            if text.is_empty() {
+                None
+            } else {
+                Some(text)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod encoding_test {
+    use super::*;
+    #[test]
+    fn simple_encoding_test() {
+        let win_ansi = SimpleEncoding::new(WIN_ANSI_ENCODING);
+        assert_eq!(win_ansi.decode(b"AKESO \x96 \x93ok\x94"), "AKESO – “ok”");
+        let standard = SimpleEncoding::new(STANDARD_ENCODING);
+        assert_eq!(standard.decode(b"it'"), "it’");
+        let mac_roman = SimpleEncoding::new(MAC_ROMAN_ENCODING);
+        assert_eq!(mac_roman.decode(b"\x8E"), "é");
+        let pdf_doc = SimpleEncoding::new(PDF_DOC_ENCODING);
+        assert_eq!(pdf_doc.decode(b"\x84\xA0\xE9"), "—€é");
+
+        let mut encoding = SimpleEncoding::new(WIN_ANSI_ENCODING);
+        encoding.apply_differences(&[
+            Object::Integer(65),
+            Object::Name(b"fi".to_vec()),
+            Object::Name(b"uni2264".to_vec()),
+            Object::Integer(1),
+            Object::Name(b"alpha".to_vec()),
+        ]);
+        assert_eq!(encoding.decode(b"\x01ABC"), "αﬁ≤C");
+    }
+}
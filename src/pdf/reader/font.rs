@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use super::{encoding::SimpleEncoding, mapper::UnicodeMap};
+
+const SPACE: u8 = b' ';
+const DEFAULT_WIDTH: f64 = 500f64;
+const DEFAULT_SPACE_WIDTH: f64 = 250f64;
+
+/// Decoding information of a font used on a page
+#[derive(Debug, Default, Clone)]
+pub struct Font {
+    to_unicode: Option<UnicodeMap>,
+    /// encoding of simple fonts (Type1, TrueType, Type3), composite fonts have none
+    encoding: Option<SimpleEncoding>,
+    /// cid to unicode recovered from the embedded font program of a composite font
+    glyph_fallback: Option<HashMap<u32, String>>,
+    /// glyph widths in thousandths of text space keyed by code (simple fonts) or cid
+    widths: HashMap<u32, f64>,
+    default_width: f64,
+}
+
+impl Font {
+    pub fn new(to_unicode: Option<UnicodeMap>, encoding: Option<SimpleEncoding>) -> Font {
+        Font {
+            to_unicode,
+            encoding,
+            glyph_fallback: None,
+            widths: HashMap::new(),
+            default_width: DEFAULT_WIDTH,
+        }
+    }
+
+    pub fn set_widths(mut self, widths: HashMap<u32, f64>, default_width: f64) -> Self {
+        self.widths = widths;
+        self.default_width = default_width;
+        self
+    }
+
+    pub fn set_glyph_fallback(mut self, glyph_fallback: Option<HashMap<u32, String>>) -> Self {
+        self.glyph_fallback = glyph_fallback;
+        self
+    }
+
+    /// split a string shown in this font into character codes, simple fonts always use single
+    /// byte codes, composite fonts follow the ToUnicode codespace or Identity-H
+    pub fn codes<'a>(&self, source: &'a [u8]) -> Vec<&'a [u8]> {
+        match (&self.encoding, &self.to_unicode) {
+            (Some(_), _) => source.chunks(1).collect(),
+            (None, Some(to_unicode)) => to_unicode.codes(source),
+            (None, None) => source.chunks(2).collect(),
+        }
+    }
+
+    /// decode a string shown in this font, ToUnicode takes precedence, the simple encoding or
+    /// the glyph fallback cover codes it does not map
+    pub fn decode(&self, source: &[u8]) -> String {
+        if let (None, Some(encoding)) = (&self.to_unicode, &self.encoding) {
+            return encoding.decode(source);
+        }
+        self.codes(source)
+            .into_iter()
+            .filter_map(|code| self.decode_code(code))
+            .collect()
+    }
+
+    /// glyph width of a code in thousandths of text space
+    pub fn width(&self, code: &[u8]) -> f64 {
+        self.widths
+            .get(&code_value(code))
+            .copied()
+            .unwrap_or(self.default_width)
+    }
+
+    /// width of the space glyph, fonts without one fall back to a quarter em
+    pub fn space_width(&self) -> f64 {
+        let width = match self.encoding {
+            Some(_) => self.widths.get(&(SPACE as u32)).copied().unwrap_or(0f64),
+            None => 0f64,
+        };
+        if width > 0f64 {
+            width
+        } else {
+            DEFAULT_SPACE_WIDTH
+        }
+    }
+
+    fn decode_code(&self, code: &[u8]) -> Option<&str> {
+        if let Some(text) = self.to_unicode.as_ref().and_then(|m| m.get(code)) {
+            return Some(text);
+        }
+        match (&self.encoding, code) {
+            (Some(encoding), [code]) => encoding.get(*code),
+            (Some(_), _) => None,
+            (None, _) => self
+                .glyph_fallback
+                .as_ref()
+                .and_then(|m| m.get(&code_value(code)))
+                .map(|s| s.as_str()),
+        }
+    }
+}
+
+fn code_value(code: &[u8]) -> u32 {
+    code.iter().fold(0u32, |acc, b| (acc << 8) | *b as u32)
+}
+
+#[cfg(test)]
+mod font_test {
+    use super::super::{encoding::WIN_ANSI_ENCODING, mapper::build_unicode_map};
+    use super::*;
+    #[test]
+    fn decode_test() {
+        let to_unicode = build_unicode_map(
+            "begincodespacerange <00> <FF> endcodespacerange beginbfchar <41> <0042> endbfchar"
+                .as_bytes(),
+        );
+        let font = Font::new(to_unicode.clone(), None);
+        assert_eq!(font.decode(b"AC"), "B");
+        let font = Font::new(to_unicode, Some(SimpleEncoding::new(WIN_ANSI_ENCODING)));
+        assert_eq!(font.decode(b"AC"), "BC");
+        let font = Font::new(None, Some(SimpleEncoding::new(WIN_ANSI_ENCODING)));
+        assert_eq!(font.decode(b"AC"), "AC");
+        assert_eq!(Font::default().decode(b"AC"), "");
+
+        let to_unicode = build_unicode_map(
+            "begincodespacerange <0000> <FFFF> endcodespacerange beginbfchar <0001> <0041> endbfchar"
+                .as_bytes(),
+        );
+        let fallback = HashMap::from([(1, "X".to_string()), (2, "中".to_string())]);
+        let font = Font::new(to_unicode, None).set_glyph_fallback(Some(fallback.clone()));
+        assert_eq!(font.decode(&[0, 1, 0, 2, 0, 3]), "A中");
+        let font = Font::default().set_glyph_fallback(Some(fallback));
+        assert_eq!(font.decode(&[0, 1, 0, 2]), "X中");
+    }
+
+    #[test]
+    fn width_test() {
+        let font = Font::new(None, Some(SimpleEncoding::new(WIN_ANSI_ENCODING)))
+            .set_widths(HashMap::from([(32, 278f64), (65, 722f64)]), 0f64);
+        assert_eq!(font.width(b"A"), 722f64);
+        assert_eq!(font.width(b"B"), 0f64);
+        assert_eq!(font.space_width(), 278f64);
+        let font = Font::new(None, None).set_widths(HashMap::from([(3, 600f64)]), 1000f64);
+        assert_eq!(font.codes(&[0, 3, 0, 4]).len(), 2);
+        assert_eq!(font.width(&[0, 3]), 600f64);
+        assert_eq!(font.width(&[0, 4]), 1000f64);
+        assert_eq!(font.space_width(), 250f64);
+    }
+}
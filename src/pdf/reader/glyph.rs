@@ -0,0 +1,1185 @@
+/// map a glyph name to unicode following the Adobe Glyph List specification: plain names are
+/// looked up in the list, `uniXXXX` and `uXXXX[XX]` are decoded, suffixes after a period are
+/// dropped and ligature components joined by underscores are mapped one by one
+pub fn glyph_to_unicode(name: &str) -> Option<String> {
+    let name = name.split('.').next().unwrap_or(name);
+    if name.is_empty() {
+        return None;
+    }
+    if name.contains('_') {
+        let text = name
+            .split('_')
+            .filter_map(component_to_unicode)
+            .collect::<String>();
+        return if text.is_empty() { None } else { Some(text) };
+    }
+    component_to_unicode(name)
+}
+
+fn component_to_unicode(name: &str) -> Option<String> {
+    if let Ok(i) = GLYPHS.binary_search_by(|(glyph, _)| (*glyph).cmp(name)) {
+        return char::from_u32(GLYPHS[i].1 as u32).map(String::from);
+    }
+    if let Some(hex) = name.strip_prefix("uni") {
+        if !hex.is_empty() && hex.len() % 4 == 0 && is_upper_hex(hex) {
+            let units = (0..hex.len())
+                .step_by(4)
+                .filter_map(|i| u16::from_str_radix(&hex[i..i + 4], 16).ok())
+                .collect::<Vec<_>>();
+            return Some(String::from_utf16_lossy(&units));
+        }
+    }
+    if let Some(hex) = name.strip_prefix('u') {
+        if (4..=6).contains(&hex.len()) && is_upper_hex(hex) {
+            return u32::from_str_radix(hex, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .map(String::from);
+        }
+    }
+    None
+}
+
+fn is_upper_hex(source: &str) -> bool {
+    source
+        .chars()
+        .all(|c| c.is_ascii_digit() || ('A'..='F').contains(&c))
+}
+
+/// Glyph names of the Adobe Glyph List covering latin, greek, punctuation, symbols and
+/// ligatures, sorted by name for binary search
+const GLYPHS: &[(&str, u16)] = &[
+    ("A", 0x0041),
+    ("AE", 0x00C6),
+    ("AEacute", 0x01FC),
+    ("AEmacron", 0x01E2),
+    ("Aacute", 0x00C1),
+    ("Abreve", 0x0102),
+    ("Acaron", 0x01CD),
+    ("Acircumflex", 0x00C2),
+    ("Adblgrave", 0x0200),
+    ("Adieresis", 0x00C4),
+    ("Adieresismacron", 0x01DE),
+    ("Adotmacron", 0x01E0),
+    ("Agrave", 0x00C0),
+    ("Ainvertedbreve", 0x0202),
+    ("Alpha", 0x0391),
+    ("Alphatonos", 0x0386),
+    ("Amacron", 0x0100),
+    ("Aogonek", 0x0104),
+    ("Aring", 0x00C5),
+    ("Aringacute", 0x01FA),
+    ("Atilde", 0x00C3),
+    ("B", 0x0042),
+    ("Beta", 0x0392),
+    ("Bhook", 0x0181),
+    ("Btopbar", 0x0182),
+    ("C", 0x0043),
+    ("Cacute", 0x0106),
+    ("Ccaron", 0x010C),
+    ("Ccedilla", 0x00C7),
+    ("Ccircumflex", 0x0108),
+    ("Cdot", 0x010A),
+    ("Cdotaccent", 0x010A),
+    ("Chi", 0x03A7),
+    ("Chook", 0x0187),
+    ("D", 0x0044),
+    ("DZ", 0x01F1),
+    ("DZcaron", 0x01C4),
+    ("Dafrican", 0x0189),
+    ("Dbar", 0x0110),
+    ("Dcaron", 0x010E),
+    ("Dcroat", 0x0110),
+    ("Deicoptic", 0x03EE),
+    ("Delta", 0x2206),
+    ("Deltagreek", 0x0394),
+    ("Dhook", 0x018A),
+    ("Digammagreek", 0x03DC),
+    ("Dslash", 0x0110),
+    ("Dtopbar", 0x018B),
+    ("Dz", 0x01F2),
+    ("Dzcaron", 0x01C5),
+    ("E", 0x0045),
+    ("Eacute", 0x00C9),
+    ("Ebreve", 0x0114),
+    ("Ecaron", 0x011A),
+    ("Ecircumflex", 0x00CA),
+    ("Edblgrave", 0x0204),
+    ("Edieresis", 0x00CB),
+    ("Edot", 0x0116),
+    ("Edotaccent", 0x0116),
+    ("Egrave", 0x00C8),
+    ("Eightroman", 0x2167),
+    ("Einvertedbreve", 0x0206),
+    ("Elevenroman", 0x216A),
+    ("Emacron", 0x0112),
+    ("Eng", 0x014A),
+    ("Eogonek", 0x0118),
+    ("Eopen", 0x0190),
+    ("Epsilon", 0x0395),
+    ("Epsilontonos", 0x0388),
+    ("Ereversed", 0x018E),
+    ("Esh", 0x01A9),
+    ("Eta", 0x0397),
+    ("Etatonos", 0x0389),
+    ("Eth", 0x00D0),
+    ("Euro", 0x20AC),
+    ("Ezh", 0x01B7),
+    ("Ezhcaron", 0x01EE),
+    ("Ezhreversed", 0x01B8),
+    ("F", 0x0046),
+    ("Feicoptic", 0x03E4),
+    ("Fhook", 0x0191),
+    ("Fiveroman", 0x2164),
+    ("Fourroman", 0x2163),
+    ("G", 0x0047),
+    ("Gacute", 0x01F4),
+    ("Gamma", 0x0393),
+    ("Gammaafrican", 0x0194),
+    ("Gangiacoptic", 0x03EA),
+    ("Gbreve", 0x011E),
+    ("Gcaron", 0x01E6),
+    ("Gcedilla", 0x0122),
+    ("Gcircumflex", 0x011C),
+    ("Gcommaaccent", 0x0122),
+    ("Gdot", 0x0120),
+    ("Gdotaccent", 0x0120),
+    ("Germandbls", 0x0053),
+    ("Ghook", 0x0193),
+    ("Gstroke", 0x01E4),
+    ("H", 0x0048),
+    ("H18533", 0x25CF),
+    ("H18543", 0x25AA),
+    ("H18551", 0x25AB),
+    ("H22073", 0x25A1),
+    ("Hbar", 0x0126),
+    ("Hcircumflex", 0x0124),
+    ("Horicoptic", 0x03E8),
+    ("I", 0x0049),
+    ("IJ", 0x0132),
+    ("Iacute", 0x00CD),
+    ("Ibreve", 0x012C),
+    ("Icaron", 0x01CF),
+    ("Icircumflex", 0x00CE),
+    ("Idblgrave", 0x0208),
+    ("Idieresis", 0x00CF),
+    ("Idot", 0x0130),
+    ("Idotaccent", 0x0130),
+    ("Ifractur", 0x2111),
+    ("Ifraktur", 0x2111),
+    ("Igrave", 0x00CC),
+    ("Iinvertedbreve", 0x020A),
+    ("Imacron", 0x012A),
+    ("Iogonek", 0x012E),
+    ("Iota", 0x0399),
+    ("Iotaafrican", 0x0196),
+    ("Iotadieresis", 0x03AA),
+    ("Iotatonos", 0x038A),
+    ("Istroke", 0x0197),
+    ("Itilde", 0x0128),
+    ("J", 0x004A),
+    ("Jcircumflex", 0x0134),
+    ("K", 0x004B),
+    ("Kappa", 0x039A),
+    ("Kcaron", 0x01E8),
+    ("Kcedilla", 0x0136),
+    ("Kcommaaccent", 0x0136),
+    ("Kheicoptic", 0x03E6),
+    ("Khook", 0x0198),
+    ("Koppagreek", 0x03DE),
+    ("L", 0x004C),
+    ("LJ", 0x01C7),
+    ("Lacute", 0x0139),
+    ("Lambda", 0x039B),
+    ("Lcaron", 0x013D),
+    ("Lcedilla", 0x013B),
+    ("Lcommaaccent", 0x013B),
+    ("Ldot", 0x013F),
+    ("Ldotaccent", 0x013F),
+    ("Lj", 0x01C8),
+    ("Lslash", 0x0141),
+    ("M", 0x004D),
+    ("Mturned", 0x019C),
+    ("Mu", 0x039C),
+    ("N", 0x004E),
+    ("NJ", 0x01CA),
+    ("Nacute", 0x0143),
+    ("Ncaron", 0x0147),
+    ("Ncedilla", 0x0145),
+    ("Ncommaaccent", 0x0145),
+    ("Ng", 0x014A),
+    ("Nhookleft", 0x019D),
+    ("Nineroman", 0x2168),
+    ("Nj", 0x01CB),
+    ("Ntilde", 0x00D1),
+    ("Nu", 0x039D),
+    ("O", 0x004F),
+    ("OE", 0x0152),
+    ("Oacute", 0x00D3),
+    ("Obreve", 0x014E),
+    ("Ocaron", 0x01D1),
+    ("Ocenteredtilde", 0x019F),
+    ("Ocircumflex", 0x00D4),
+    ("Odblacute", 0x0150),
+    ("Odblgrave", 0x020C),
+    ("Odieresis", 0x00D6),
+    ("Ograve", 0x00D2),
+    ("Ohm", 0x2126),
+    ("Ohorn", 0x01A0),
+    ("Ohungarumlaut", 0x0150),
+    ("Oi", 0x01A2),
+    ("Oinvertedbreve", 0x020E),
+    ("Omacron", 0x014C),
+    ("Omega", 0x2126),
+    ("Omegagreek", 0x03A9),
+    ("Omegatonos", 0x038F),
+    ("Omicron", 0x039F),
+    ("Omicrontonos", 0x038C),
+    ("Oneroman", 0x2160),
+    ("Oogonek", 0x01EA),
+    ("Oogonekmacron", 0x01EC),
+    ("Oopen", 0x0186),
+    ("Oslash", 0x00D8),
+    ("Oslashacute", 0x01FE),
+    ("Ostrokeacute", 0x01FE),
+    ("Otilde", 0x00D5),
+    ("P", 0x0050),
+    ("Phi", 0x03A6),
+    ("Phook", 0x01A4),
+    ("Pi", 0x03A0),
+    ("Psi", 0x03A8),
+    ("Q", 0x0051),
+    ("R", 0x0052),
+    ("Racute", 0x0154),
+    ("Rcaron", 0x0158),
+    ("Rcedilla", 0x0156),
+    ("Rcommaaccent", 0x0156),
+    ("Rdblgrave", 0x0210),
+    ("Rfractur", 0x211C),
+    ("Rfraktur", 0x211C),
+    ("Rho", 0x03A1),
+    ("Rinvertedbreve", 0x0212),
+    ("S", 0x0053),
+    ("SS", 0x0053),
+    ("Sacute", 0x015A),
+    ("Sampigreek", 0x03E0),
+    ("Scaron", 0x0160),
+    ("Scedilla", 0x015E),
+    ("Schwa", 0x018F),
+    ("Scircumflex", 0x015C),
+    ("Scommaaccent", 0x0218),
+    ("Sevenroman", 0x2166),
+    ("Sheicoptic", 0x03E2),
+    ("Shimacoptic", 0x03EC),
+    ("Sigma", 0x03A3),
+    ("Sixroman", 0x2165),
+    ("Stigmagreek", 0x03DA),
+    ("T", 0x0054),
+    ("Tau", 0x03A4),
+    ("Tbar", 0x0166),
+    ("Tcaron", 0x0164),
+    ("Tcedilla", 0x0162),
+    ("Tcommaaccent", 0x0162),
+    ("Tenroman", 0x2169),
+    ("Theta", 0x0398),
+    ("Thook", 0x01AC),
+    ("Thorn", 0x00DE),
+    ("Threeroman", 0x2162),
+    ("Tonefive", 0x01BC),
+    ("Tonesix", 0x0184),
+    ("Tonetwo", 0x01A7),
+    ("Tretroflexhook", 0x01AE),
+    ("Twelveroman", 0x216B),
+    ("Tworoman", 0x2161),
+    ("U", 0x0055),
+    ("Uacute", 0x00DA),
+    ("Ubreve", 0x016C),
+    ("Ucaron", 0x01D3),
+    ("Ucircumflex", 0x00DB),
+    ("Udblacute", 0x0170),
+    ("Udblgrave", 0x0214),
+    ("Udieresis", 0x00DC),
+    ("Udieresisacute", 0x01D7),
+    ("Udieresiscaron", 0x01D9),
+    ("Udieresisgrave", 0x01DB),
+    ("Udieresismacron", 0x01D5),
+    ("Ugrave", 0x00D9),
+    ("Uhorn", 0x01AF),
+    ("Uhungarumlaut", 0x0170),
+    ("Uinvertedbreve", 0x0216),
+    ("Umacron", 0x016A),
+    ("Uogonek", 0x0172),
+    ("Upsilon", 0x03A5),
+    ("Upsilon1", 0x03D2),
+    ("Upsilonacutehooksymbolgreek", 0x03D3),
+    ("Upsilonafrican", 0x01B1),
+    ("Upsilondieresis", 0x03AB),
+    ("Upsilondieresishooksymbolgreek", 0x03D4),
+    ("Upsilonhooksymbol", 0x03D2),
+    ("Upsilontonos", 0x038E),
+    ("Uring", 0x016E),
+    ("Utilde", 0x0168),
+    ("V", 0x0056),
+    ("Vhook", 0x01B2),
+    ("W", 0x0057),
+    ("Wcircumflex", 0x0174),
+    ("X", 0x0058),
+    ("Xi", 0x039E),
+    ("Y", 0x0059),
+    ("Yacute", 0x00DD),
+    ("Ycircumflex", 0x0176),
+    ("Ydieresis", 0x0178),
+    ("Yhook", 0x01B3),
+    ("Z", 0x005A),
+    ("Zacute", 0x0179),
+    ("Zcaron", 0x017D),
+    ("Zdot", 0x017B),
+    ("Zdotaccent", 0x017B),
+    ("Zeta", 0x0396),
+    ("Zstroke", 0x01B5),
+    ("a", 0x0061),
+    ("aacute", 0x00E1),
+    ("abreve", 0x0103),
+    ("acaron", 0x01CE),
+    ("acircumflex", 0x00E2),
+    ("acute", 0x00B4),
+    ("adblgrave", 0x0201),
+    ("adieresis", 0x00E4),
+    ("adieresismacron", 0x01DF),
+    ("adotmacron", 0x01E1),
+    ("ae", 0x00E6),
+    ("aeacute", 0x01FD),
+    ("aemacron", 0x01E3),
+    ("afii00208", 0x2015),
+    ("afii08941", 0x20A4),
+    ("afii299", 0x200E),
+    ("afii300", 0x200F),
+    ("afii301", 0x200D),
+    ("afii57636", 0x20AA),
+    ("afii61248", 0x2105),
+    ("afii61289", 0x2113),
+    ("afii61352", 0x2116),
+    ("afii61573", 0x202C),
+    ("afii61574", 0x202D),
+    ("afii61575", 0x202E),
+    ("afii61664", 0x200C),
+    ("agrave", 0x00E0),
+    ("ainvertedbreve", 0x0203),
+    ("aleph", 0x2135),
+    ("allequal", 0x224C),
+    ("alpha", 0x03B1),
+    ("alphatonos", 0x03AC),
+    ("amacron", 0x0101),
+    ("ampersand", 0x0026),
+    ("angle", 0x2220),
+    ("angstrom", 0x212B),
+    ("anoteleia", 0x0387),
+    ("aogonek", 0x0105),
+    ("approaches", 0x2250),
+    ("approxequal", 0x2248),
+    ("approxequalorimage", 0x2252),
+    ("approximatelyequal", 0x2245),
+    ("aring", 0x00E5),
+    ("aringacute", 0x01FB),
+    ("arrowboth", 0x2194),
+    ("arrowbothv", 0x2195),
+    ("arrowbt", 0x2193),
+    ("arrowdashdown", 0x21E3),
+    ("arrowdashleft", 0x21E0),
+    ("arrowdashright", 0x21E2),
+    ("arrowdashup", 0x21E1),
+    ("arrowdblboth", 0x21D4),
+    ("arrowdblbothv", 0x21D5),
+    ("arrowdbldown", 0x21D3),
+    ("arrowdblleft", 0x21D0),
+    ("arrowdblright", 0x21D2),
+    ("arrowdbltp", 0x21D1),
+    ("arrowdblup", 0x21D1),
+    ("arrowdblvertex", 0x21D5),
+    ("arrowdown", 0x2193),
+    ("arrowdownleft", 0x2199),
+    ("arrowdownright", 0x2198),
+    ("arrowdownwhite", 0x21E9),
+    ("arrowleft", 0x2190),
+    ("arrowleftbothalf", 0x21BD),
+    ("arrowleftdbl", 0x21D0),
+    ("arrowleftdblstroke", 0x21CD),
+    ("arrowleftoverright", 0x21C6),
+    ("arrowlefttophalf", 0x21BC),
+    ("arrowleftwhite", 0x21E6),
+    ("arrownortheast", 0x2197),
+    ("arrownorthwest", 0x2196),
+    ("arrowright", 0x2192),
+    ("arrowrightbothalf", 0x21C1),
+    ("arrowrightdblstroke", 0x21CF),
+    ("arrowrightoverleft", 0x21C4),
+    ("arrowrighttophalf", 0x21C0),
+    ("arrowrightwhite", 0x21E8),
+    ("arrowsoutheast", 0x2198),
+    ("arrowsouthwest", 0x2199),
+    ("arrowtableft", 0x21E4),
+    ("arrowtabright", 0x21E5),
+    ("arrowtp", 0x2191),
+    ("arrowup", 0x2191),
+    ("arrowupdn", 0x2195),
+    ("arrowupdnbse", 0x21A8),
+    ("arrowupdownbase", 0x21A8),
+    ("arrowupleft", 0x2196),
+    ("arrowupleftofdown", 0x21C5),
+    ("arrowupright", 0x2197),
+    ("arrowupwhite", 0x21E7),
+    ("arrowvertex", 0x2195),
+    ("asciicircum", 0x005E),
+    ("asciitilde", 0x007E),
+    ("asterisk", 0x002A),
+    ("asteriskcentered", 0x2217),
+    ("asteriskmath", 0x2217),
+    ("asterism", 0x2042),
+    ("asymptoticallyequal", 0x2243),
+    ("at", 0x0040),
+    ("atilde", 0x00E3),
+    ("b", 0x0062),
+    ("backslash", 0x005C),
+    ("backslashBig", 0x005C),
+    ("backslashBigg", 0x005C),
+    ("backslashbig", 0x005C),
+    ("backslashbigg", 0x005C),
+    ("bar", 0x007C),
+    ("bardbl", 0x2225),
+    ("bardblex", 0x2016),
+    ("barex", 0x007C),
+    ("because", 0x2235),
+    ("beta", 0x03B2),
+    ("betasymbolgreek", 0x03D0),
+    ("blackcircle", 0x25CF),
+    ("blackdiamond", 0x25C6),
+    ("blackdownpointingtriangle", 0x25BC),
+    ("blackleftpointingpointer", 0x25C4),
+    ("blackleftpointingtriangle", 0x25C0),
+    ("blacklowerlefttriangle", 0x25E3),
+    ("blacklowerrighttriangle", 0x25E2),
+    ("blackrectangle", 0x25AC),
+    ("blackrightpointingpointer", 0x25BA),
+    ("blackrightpointingtriangle", 0x25B6),
+    ("blacksmallsquare", 0x25AA),
+    ("blacksquare", 0x25A0),
+    ("blackupperlefttriangle", 0x25E4),
+    ("blackupperrighttriangle", 0x25E5),
+    ("blackuppointingsmalltriangle", 0x25B4),
+    ("blackuppointingtriangle", 0x25B2),
+    ("braceex", 0x007C),
+    ("braceleft", 0x007B),
+    ("braceleftBig", 0x007B),
+    ("braceleftBigg", 0x007B),
+    ("braceleftbig", 0x007B),
+    ("braceleftbigg", 0x007B),
+    ("braceleftmid", 0x007C),
+    ("braceright", 0x007D),
+    ("bracerightBig", 0x007D),
+    ("bracerightBigg", 0x007D),
+    ("bracerightbig", 0x007D),
+    ("bracerightbigg", 0x007D),
+    ("bracerightmid", 0x2016),
+    ("bracketleft", 0x005B),
+    ("bracketleftBig", 0x005B),
+    ("bracketleftBigg", 0x005B),
+    ("bracketleftbig", 0x005B),
+    ("bracketleftbigg", 0x005B),
+    ("bracketright", 0x005D),
+    ("bracketrightBig", 0x005D),
+    ("bracketrightBigg", 0x005D),
+    ("bracketrightbig", 0x005D),
+    ("bracketrightbigg", 0x005D),
+    ("brokenbar", 0x00A6),
+    ("bstroke", 0x0180),
+    ("btopbar", 0x0183),
+    ("bullet", 0x2022),
+    ("bulletinverse", 0x25D8),
+    ("bulletoperator", 0x2219),
+    ("bullseye", 0x25CE),
+    ("c", 0x0063),
+    ("cacute", 0x0107),
+    ("capslock", 0x21EA),
+    ("careof", 0x2105),
+    ("carriagereturn", 0x21B5),
+    ("ccaron", 0x010D),
+    ("ccedilla", 0x00E7),
+    ("ccircumflex", 0x0109),
+    ("cdot", 0x010B),
+    ("cdotaccent", 0x010B),
+    ("cedilla", 0x00B8),
+    ("cent", 0x00A2),
+    ("centigrade", 0x2103),
+    ("chi", 0x03C7),
+    ("chook", 0x0188),
+    ("circle", 0x25CB),
+    ("circlecopyrt", 0x20DD),
+    ("circledivide", 0x2298),
+    ("circledot", 0x2299),
+    ("circledotdisplay", 0x2299),
+    ("circledottext", 0x2299),
+    ("circleminus", 0x2296),
+    ("circlemultiply", 0x2297),
+    ("circlemultiplydisplay", 0x2297),
+    ("circlemultiplytext", 0x2297),
+    ("circleot", 0x2299),
+    ("circleplus", 0x2295),
+    ("circleplusdisplay", 0x2295),
+    ("circleplustext", 0x2295),
+    ("circlewithlefthalfblack", 0x25D0),
+    ("circlewithrighthalfblack", 0x25D1),
+    ("clickalveolar", 0x01C2),
+    ("clickdental", 0x01C0),
+    ("clicklateral", 0x01C1),
+    ("clickretroflex", 0x01C3),
+    ("colon", 0x003A),
+    ("colonmonetary", 0x20A1),
+    ("colonsign", 0x20A1),
+    ("comma", 0x002C),
+    ("compwordmark", 0x200C),
+    ("congruent", 0x2245),
+    ("contintegraldisplay", 0x222E),
+    ("contintegraltext", 0x222E),
+    ("contourintegral", 0x222E),
+    ("controlDEL", 0x007F),
+    ("coproductdisplay", 0x2210),
+    ("coproducttext", 0x2210),
+    ("copyright", 0x00A9),
+    ("cruzeiro", 0x20A2),
+    ("curlyand", 0x22CF),
+    ("curlyor", 0x22CE),
+    ("currency", 0x00A4),
+    ("cwm", 0x200C),
+    ("d", 0x0064),
+    ("dagger", 0x2020),
+    ("daggerdbl", 0x2021),
+    ("dbar", 0x0111),
+    ("dblarrowleft", 0x21D4),
+    ("dblarrowright", 0x21D2),
+    ("dblintegral", 0x222C),
+    ("dbllowline", 0x2017),
+    ("dblverticalbar", 0x2016),
+    ("dcaron", 0x010F),
+    ("dcroat", 0x0111),
+    ("degree", 0x00B0),
+    ("deicoptic", 0x03EF),
+    ("delta", 0x03B4),
+    ("deltaturned", 0x018D),
+    ("dialytikatonos", 0x0385),
+    ("diamondmath", 0x22C4),
+    ("dieresis", 0x00A8),
+    ("dieresistonos", 0x0385),
+    ("divide", 0x00F7),
+    ("divides", 0x2223),
+    ("divisionslash", 0x2215),
+    ("dmacron", 0x0111),
+    ("dollar", 0x0024),
+    ("dong", 0x20AB),
+    ("dotlessi", 0x0131),
+    ("dotlessj", 0x0237),
+    ("dotmath", 0x22C5),
+    ("dottedcircle", 0x25CC),
+    ("dtopbar", 0x018C),
+    ("dz", 0x01F3),
+    ("dzcaron", 0x01C6),
+    ("e", 0x0065),
+    ("eacute", 0x00E9),
+    ("ebreve", 0x0115),
+    ("ecaron", 0x011B),
+    ("ecircumflex", 0x00EA),
+    ("edblgrave", 0x0205),
+    ("edieresis", 0x00EB),
+    ("edot", 0x0117),
+    ("edotaccent", 0x0117),
+    ("egrave", 0x00E8),
+    ("eight", 0x0038),
+    ("eightinferior", 0x2088),
+    ("eightroman", 0x2177),
+    ("eightsuperior", 0x2078),
+    ("einvertedbreve", 0x0207),
+    ("element", 0x2208),
+    ("elevenroman", 0x217A),
+    ("ellipsis", 0x2026),
+    ("ellipsisvertical", 0x22EE),
+    ("emacron", 0x0113),
+    ("emdash", 0x2014),
+    ("emptyset", 0x2205),
+    ("endash", 0x2013),
+    ("eng", 0x014B),
+    ("enspace", 0x2002),
+    ("eogonek", 0x0119),
+    ("epsilon", 0x03B5),
+    ("epsilon1", 0x03F5),
+    ("epsilontonos", 0x03AD),
+    ("equal", 0x003D),
+    ("equalsuperior", 0x207C),
+    ("equivalence", 0x2261),
+    ("equivasymptotic", 0x224D),
+    ("eshreversedloop", 0x01AA),
+    ("estimated", 0x212E),
+    ("eta", 0x03B7),
+    ("etatonos", 0x03AE),
+    ("eth", 0x00F0),
+    ("eturned", 0x01DD),
+    ("euro", 0x20AC),
+    ("exclam", 0x0021),
+    ("exclamdbl", 0x203C),
+    ("exclamdown", 0x00A1),
+    ("existential", 0x2203),
+    ("ezhcaron", 0x01EF),
+    ("ezhreversed", 0x01B9),
+    ("ezhtail", 0x01BA),
+    ("f", 0x0066),
+    ("f_f", 0xFB00),
+    ("f_f_i", 0xFB03),
+    ("f_f_l", 0xFB04),
+    ("f_i", 0xFB01),
+    ("f_l", 0xFB02),
+    ("fahrenheit", 0x2109),
+    ("feicoptic", 0x03E5),
+    ("ff", 0xFB00),
+    ("ffi", 0xFB03),
+    ("ffl", 0xFB04),
+    ("fi", 0xFB01),
+    ("figuredash", 0x2012),
+    ("filledbox", 0x25A0),
+    ("filledrect", 0x25AC),
+    ("fisheye", 0x25C9),
+    ("five", 0x0035),
+    ("fiveeighths", 0x215D),
+    ("fiveinferior", 0x2085),
+    ("fiveroman", 0x2174),
+    ("fivesuperior", 0x2075),
+    ("fl", 0xFB02),
+    ("florin", 0x0192),
+    ("follows", 0x227B),
+    ("followsequal", 0x227D),
+    ("forall", 0x2200),
+    ("four", 0x0034),
+    ("fourinferior", 0x2084),
+    ("fourroman", 0x2173),
+    ("foursuperior", 0x2074),
+    ("fraction", 0x2044),
+    ("franc", 0x20A3),
+    ("g", 0x0067),
+    ("gacute", 0x01F5),
+    ("gamma", 0x03B3),
+    ("gangiacoptic", 0x03EB),
+    ("gbreve", 0x011F),
+    ("gcaron", 0x01E7),
+    ("gcedilla", 0x0123),
+    ("gcircumflex", 0x011D),
+    ("gcommaaccent", 0x0123),
+    ("gdot", 0x0121),
+    ("gdotaccent", 0x0121),
+    ("geometricallyequal", 0x2251),
+    ("germandbls", 0x00DF),
+    ("glottalinvertedstroke", 0x01BE),
+    ("gradient", 0x2207),
+    ("grave", 0x0060),
+    ("greater", 0x003E),
+    ("greaterequal", 0x2265),
+    ("greaterequalorless", 0x22DB),
+    ("greatermuch", 0x226B),
+    ("greaterorequivalent", 0x2273),
+    ("greaterorless", 0x2277),
+    ("greateroverequal", 0x2267),
+    ("gstroke", 0x01E5),
+    ("guillemotleft", 0x00AB),
+    ("guillemotright", 0x00BB),
+    ("guilsinglleft", 0x2039),
+    ("guilsinglright", 0x203A),
+    ("h", 0x0068),
+    ("harpoonleftbarbup", 0x21BC),
+    ("harpoonleftdown", 0x21BD),
+    ("harpoonleftup", 0x21BC),
+    ("harpoonrightbarbup", 0x21C0),
+    ("harpoonrightdown", 0x21C1),
+    ("harpoonrightup", 0x21C0),
+    ("hbar", 0x0127),
+    ("hcircumflex", 0x0125),
+    ("hookleftchar", 0x21A9),
+    ("hookrightchar", 0x21AA),
+    ("horicoptic", 0x03E9),
+    ("horizontalbar", 0x2015),
+    ("hv", 0x0195),
+    ("hyphen", 0x002D),
+    ("hyphen_alt", 0x2010),
+    ("hyphenchar", 0x002D),
+    ("hyphentwo", 0x2010),
+    ("i", 0x0069),
+    ("iacute", 0x00ED),
+    ("ibreve", 0x012D),
+    ("icaron", 0x01D0),
+    ("icircumflex", 0x00EE),
+    ("idblgrave", 0x0209),
+    ("idieresis", 0x00EF),
+    ("igrave", 0x00EC),
+    ("iinvertedbreve", 0x020B),
+    ("ij", 0x0133),
+    ("imacron", 0x012B),
+    ("imageorapproximatelyequal", 0x2253),
+    ("increment", 0x2206),
+    ("infinity", 0x221E),
+    ("integral", 0x222B),
+    ("integraldisplay", 0x222B),
+    ("integraltext", 0x222B),
+    ("interrobang", 0x203D),
+    ("intersection", 0x2229),
+    ("intersectiondisplay", 0x22C2),
+    ("intersectionsq", 0x2293),
+    ("intersectiontext", 0x22C2),
+    ("invbullet", 0x25D8),
+    ("invcircle", 0x25D9),
+    ("iogonek", 0x012F),
+    ("iota", 0x03B9),
+    ("iotadieresis", 0x03CA),
+    ("iotadieresistonos", 0x0390),
+    ("iotatonos", 0x03AF),
+    ("itilde", 0x0129),
+    ("j", 0x006A),
+    ("jcaron", 0x01F0),
+    ("jcircumflex", 0x0135),
+    ("k", 0x006B),
+    ("kappa", 0x03BA),
+    ("kappasymbolgreek", 0x03F0),
+    ("kcaron", 0x01E9),
+    ("kcedilla", 0x0137),
+    ("kcommaaccent", 0x0137),
+    ("kgreenlandic", 0x0138),
+    ("kheicoptic", 0x03E7),
+    ("khook", 0x0199),
+    ("l", 0x006C),
+    ("lacute", 0x013A),
+    ("lambda", 0x03BB),
+    ("lambdastroke", 0x019B),
+    ("largecircle", 0x25EF),
+    ("latticetop", 0x22A4),
+    ("lbar", 0x019A),
+    ("lcaron", 0x013E),
+    ("lcedilla", 0x013C),
+    ("lcommaaccent", 0x013C),
+    ("ldot", 0x0140),
+    ("ldotaccent", 0x0140),
+    ("less", 0x003C),
+    ("lessequal", 0x2264),
+    ("lessequalorgreater", 0x22DA),
+    ("lessmuch", 0x226A),
+    ("lessorequivalent", 0x2272),
+    ("lessorgreater", 0x2276),
+    ("lessoverequal", 0x2266),
+    ("lira", 0x20A4),
+    ("lj", 0x01C9),
+    ("logicaland", 0x2227),
+    ("logicalanddisplay", 0x22C0),
+    ("logicalandtext", 0x22C0),
+    ("logicalnot", 0x00AC),
+    ("logicalor", 0x2228),
+    ("logicalordisplay", 0x22C1),
+    ("logicalortext", 0x22C1),
+    ("longs", 0x017F),
+    ("lozenge", 0x25CA),
+    ("lscript", 0x2113),
+    ("lslash", 0x0142),
+    ("lsquare", 0x2113),
+    ("m", 0x006D),
+    ("macron", 0x00AF),
+    ("middot", 0x00B7),
+    ("minus", 0x2212),
+    ("minuscircle", 0x2296),
+    ("minusplus", 0x2213),
+    ("minute", 0x2032),
+    ("mu", 0x00B5),
+    ("mu1", 0x00B5),
+    ("muchgreater", 0x226B),
+    ("muchless", 0x226A),
+    ("mugreek", 0x03BC),
+    ("multiply", 0x00D7),
+    ("n", 0x006E),
+    ("nabla", 0x2207),
+    ("nacute", 0x0144),
+    ("napostrophe", 0x0149),
+    ("nbspace", 0x00A0),
+    ("ncaron", 0x0148),
+    ("ncedilla", 0x0146),
+    ("ncommaaccent", 0x0146),
+    ("newsheqelsign", 0x20AA),
+    ("ng", 0x014B),
+    ("nine", 0x0039),
+    ("nineinferior", 0x2089),
+    ("nineroman", 0x2178),
+    ("ninesuperior", 0x2079),
+    ("nj", 0x01CC),
+    ("nlegrightlong", 0x019E),
+    ("nonbreakingspace", 0x00A0),
+    ("notcontains", 0x220C),
+    ("notelement", 0x2209),
+    ("notelementof", 0x2209),
+    ("notequal", 0x2260),
+    ("notgreater", 0x226F),
+    ("notgreaternorequal", 0x2271),
+    ("notgreaternorless", 0x2279),
+    ("notidentical", 0x2262),
+    ("notless", 0x226E),
+    ("notlessnorequal", 0x2270),
+    ("notparallel", 0x2226),
+    ("notprecedes", 0x2280),
+    ("notsubset", 0x2284),
+    ("notsucceeds", 0x2281),
+    ("notsuperset", 0x2285),
+    ("nsuperior", 0x207F),
+    ("ntilde", 0x00F1),
+    ("nu", 0x03BD),
+    ("numbersign", 0x0023),
+    ("numeralsigngreek", 0x0374),
+    ("numeralsignlowergreek", 0x0375),
+    ("numero", 0x2116),
+    ("o", 0x006F),
+    ("oacute", 0x00F3),
+    ("obreve", 0x014F),
+    ("ocaron", 0x01D2),
+    ("ocircumflex", 0x00F4),
+    ("odblacute", 0x0151),
+    ("odblgrave", 0x020D),
+    ("odieresis", 0x00F6),
+    ("oe", 0x0153),
+    ("ograve", 0x00F2),
+    ("ohorn", 0x01A1),
+    ("ohungarumlaut", 0x0151),
+    ("oi", 0x01A3),
+    ("oinvertedbreve", 0x020F),
+    ("omacron", 0x014D),
+    ("omega", 0x03C9),
+    ("omega1", 0x03D6),
+    ("omegatonos", 0x03CE),
+    ("omicron", 0x03BF),
+    ("omicrontonos", 0x03CC),
+    ("one", 0x0031),
+    ("onedotenleader", 0x2024),
+    ("oneeighth", 0x215B),
+    ("onehalf", 0x00BD),
+    ("oneinferior", 0x2081),
+    ("onequarter", 0x00BC),
+    ("oneroman", 0x2170),
+    ("onesuperior", 0x00B9),
+    ("onethird", 0x2153),
+    ("oogonek", 0x01EB),
+    ("oogonekmacron", 0x01ED),
+    ("openbullet", 0x25E6),
+    ("ordfeminine", 0x00AA),
+    ("ordmasculine", 0x00BA),
+    ("orthogonal", 0x221F),
+    ("oslash", 0x00F8),
+    ("oslashacute", 0x01FF),
+    ("ostrokeacute", 0x01FF),
+    ("otilde", 0x00F5),
+    ("overline", 0x203E),
+    ("overscore", 0x00AF),
+    ("owner", 0x220B),
+    ("p", 0x0070),
+    ("pagedown", 0x21DF),
+    ("pageup", 0x21DE),
+    ("paragraph", 0x00B6),
+    ("parallel", 0x2225),
+    ("parenleft", 0x0028),
+    ("parenleftBig", 0x0028),
+    ("parenleftBigg", 0x0028),
+    ("parenleftbig", 0x0028),
+    ("parenleftbigg", 0x0028),
+    ("parenleftex", 0x007C),
+    ("parenleftinferior", 0x208D),
+    ("parenleftsuperior", 0x207D),
+    ("parenright", 0x0029),
+    ("parenrightBig", 0x0029),
+    ("parenrightBigg", 0x0029),
+    ("parenrightbig", 0x0029),
+    ("parenrightbigg", 0x0029),
+    ("parenrightex", 0x007C),
+    ("parenrightinferior", 0x208E),
+    ("parenrightsuperior", 0x207E),
+    ("partialdiff", 0x2202),
+    ("percent", 0x0025),
+    ("period", 0x002E),
+    ("periodcentered", 0x00B7),
+    ("perpendicular", 0x22A5),
+    ("pertenthousand", 0x2031),
+    ("perthousand", 0x2030),
+    ("peseta", 0x20A7),
+    ("phi", 0x03C6),
+    ("phi2", 0x03D5),
+    ("phisymbolgreek", 0x03D5),
+    ("phook", 0x01A5),
+    ("pi", 0x03C0),
+    ("pi1", 0x03D6),
+    ("pisymbolgreek", 0x03D6),
+    ("plus", 0x002B),
+    ("pluscircle", 0x2295),
+    ("plusminus", 0x00B1),
+    ("plussuperior", 0x207A),
+    ("precedes", 0x227A),
+    ("precedesequal", 0x227C),
+    ("prescription", 0x211E),
+    ("prime", 0x2032),
+    ("primereversed", 0x2035),
+    ("product", 0x220F),
+    ("productdisplay", 0x220F),
+    ("producttext", 0x220F),
+    ("propersubset", 0x2282),
+    ("propersuperset", 0x2283),
+    ("proportion", 0x2237),
+    ("proportional", 0x221D),
+    ("psi", 0x03C8),
+    ("punctdash", 0x2014),
+    ("q", 0x0071),
+    ("question", 0x003F),
+    ("questiondown", 0x00BF),
+    ("questiongreek", 0x037E),
+    ("quotedbl", 0x0022),
+    ("quotedblbase", 0x201E),
+    ("quotedblleft", 0x201C),
+    ("quotedblright", 0x201D),
+    ("quoteleft", 0x2018),
+    ("quoteleftreversed", 0x201B),
+    ("quotereversed", 0x201B),
+    ("quoteright", 0x2019),
+    ("quoterightn", 0x0149),
+    ("quotesinglbase", 0x201A),
+    ("quotesingle", 0x0027),
+    ("r", 0x0072),
+    ("racute", 0x0155),
+    ("radical", 0x221A),
+    ("radicalBig", 0x221A),
+    ("radicalBigg", 0x221A),
+    ("radicalbig", 0x221A),
+    ("radicalbigg", 0x221A),
+    ("radicalbt", 0x221A),
+    ("rangedash", 0x2013),
+    ("ratio", 0x2236),
+    ("rcaron", 0x0159),
+    ("rcedilla", 0x0157),
+    ("rcommaaccent", 0x0157),
+    ("rdblgrave", 0x0211),
+    ("referencemark", 0x203B),
+    ("reflexsubset", 0x2286),
+    ("reflexsuperset", 0x2287),
+    ("registered", 0x00AE),
+    ("reversedtilde", 0x223D),
+    ("rho", 0x03C1),
+    ("rho1", 0x03F1),
+    ("rhosymbolgreek", 0x03F1),
+    ("rightangle", 0x221F),
+    ("righttriangle", 0x22BF),
+    ("rinvertedbreve", 0x0213),
+    ("s", 0x0073),
+    ("sacute", 0x015B),
+    ("scaron", 0x0161),
+    ("scedilla", 0x015F),
+    ("scircumflex", 0x015D),
+    ("scommaaccent", 0x0219),
+    ("second", 0x2033),
+    ("section", 0x00A7),
+    ("semicolon", 0x003B),
+    ("seven", 0x0037),
+    ("seveneighths", 0x215E),
+    ("seveninferior", 0x2087),
+    ("sevenroman", 0x2176),
+    ("sevensuperior", 0x2077),
+    ("sfthyphen", 0x00AD),
+    ("sheicoptic", 0x03E3),
+    ("sheqel", 0x20AA),
+    ("sheqelhebrew", 0x20AA),
+    ("shimacoptic", 0x03ED),
+    ("sigma", 0x03C3),
+    ("sigma1", 0x03C2),
+    ("sigmafinal", 0x03C2),
+    ("sigmalunatesymbolgreek", 0x03F2),
+    ("similar", 0x223C),
+    ("similarequal", 0x2243),
+    ("six", 0x0036),
+    ("sixinferior", 0x2086),
+    ("sixroman", 0x2175),
+    ("sixsuperior", 0x2076),
+    ("slash", 0x002F),
+    ("slashBig", 0x2215),
+    ("slashBigg", 0x2215),
+    ("slashbig", 0x2215),
+    ("slashbigg", 0x2215),
+    ("slong", 0x017F),
+    ("softhyphen", 0x00AD),
+    ("space", 0x0020),
+    ("spacehackarabic", 0x0020),
+    ("squarediagonalcrosshatchfill", 0x25A9),
+    ("squarehorizontalfill", 0x25A4),
+    ("squareorthogonalcrosshatchfill", 0x25A6),
+    ("squareupperlefttolowerrightfill", 0x25A7),
+    ("squareupperrighttolowerleftfill", 0x25A8),
+    ("squareverticalfill", 0x25A5),
+    ("squarewhitewithsmallblack", 0x25A3),
+    ("star", 0x22C6),
+    ("sterling", 0x00A3),
+    ("subset", 0x2282),
+    ("subsetnotequal", 0x228A),
+    ("subsetorequal", 0x2286),
+    ("subsetsqequal", 0x2291),
+    ("succeeds", 0x227B),
+    ("suchthat", 0x220B),
+    ("summation", 0x2211),
+    ("summationdisplay", 0x2211),
+    ("summationtext", 0x2211),
+    ("superset", 0x2283),
+    ("supersetnotequal", 0x228B),
+    ("supersetorequal", 0x2287),
+    ("supersetsqequal", 0x2292),
+    ("t", 0x0074),
+    ("tackdown", 0x22A4),
+    ("tackleft", 0x22A3),
+    ("tau", 0x03C4),
+    ("tbar", 0x0167),
+    ("tcaron", 0x0165),
+    ("tcedilla", 0x0163),
+    ("tcommaaccent", 0x0163),
+    ("telephone", 0x2121),
+    ("tenroman", 0x2179),
+    ("thereexists", 0x2203),
+    ("therefore", 0x2234),
+    ("theta", 0x03B8),
+    ("theta1", 0x03D1),
+    ("thetasymbolgreek", 0x03D1),
+    ("thook", 0x01AD),
+    ("thorn", 0x00FE),
+    ("three", 0x0033),
+    ("threeeighths", 0x215C),
+    ("threeinferior", 0x2083),
+    ("threequarters", 0x00BE),
+    ("threeroman", 0x2172),
+    ("threesuperior", 0x00B3),
+    ("tie", 0x2040),
+    ("tildeoperator", 0x223C),
+    ("timescircle", 0x2297),
+    ("tonefive", 0x01BD),
+    ("tonesix", 0x0185),
+    ("tonetwo", 0x01A8),
+    ("tonos", 0x0384),
+    ("tpalatalhook", 0x01AB),
+    ("trademark", 0x2122),
+    ("triagdn", 0x25BC),
+    ("triaglf", 0x25C4),
+    ("triagrt", 0x25BA),
+    ("triagup", 0x25B2),
+    ("triangle", 0x25B3),
+    ("triangleinv", 0x25BD),
+    ("triangleleft", 0x25B9),
+    ("triangleright", 0x25C3),
+    ("turnstileleft", 0x22A2),
+    ("turnstileright", 0x22A3),
+    ("twelveroman", 0x217B),
+    ("two", 0x0032),
+    ("twodotenleader", 0x2025),
+    ("twodotleader", 0x2025),
+    ("twoinferior", 0x2082),
+    ("tworoman", 0x2171),
+    ("twostroke", 0x01BB),
+    ("twosuperior", 0x00B2),
+    ("twothirds", 0x2154),
+    ("u", 0x0075),
+    ("uacute", 0x00FA),
+    ("ubreve", 0x016D),
+    ("ucaron", 0x01D4),
+    ("ucircumflex", 0x00FB),
+    ("udblacute", 0x0171),
+    ("udblgrave", 0x0215),
+    ("udieresis", 0x00FC),
+    ("udieresisacute", 0x01D8),
+    ("udieresiscaron", 0x01DA),
+    ("udieresisgrave", 0x01DC),
+    ("udieresismacron", 0x01D6),
+    ("ugrave", 0x00F9),
+    ("uhorn", 0x01B0),
+    ("uhungarumlaut", 0x0171),
+    ("uinvertedbreve", 0x0217),
+    ("umacron", 0x016B),
+    ("underscore", 0x005F),
+    ("underscoredbl", 0x2017),
+    ("union", 0x222A),
+    ("uniondisplay", 0x22C3),
+    ("unionmulti", 0x228E),
+    ("unionmultidisplay", 0x228E),
+    ("unionmultitext", 0x228E),
+    ("unionsq", 0x2294),
+    ("unionsqdisplay", 0x2294),
+    ("unionsqtext", 0x2294),
+    ("uniontext", 0x22C3),
+    ("universal", 0x2200),
+    ("uogonek", 0x0173),
+    ("upsilon", 0x03C5),
+    ("upsilondieresis", 0x03CB),
+    ("upsilondieresistonos", 0x03B0),
+    ("upsilontonos", 0x03CD),
+    ("uring", 0x016F),
+    ("utilde", 0x0169),
+    ("v", 0x0076),
+    ("vector", 0x20D7),
+    ("verticalbar", 0x007C),
+    ("w", 0x0077),
+    ("wcircumflex", 0x0175),
+    ("weierstrass", 0x2118),
+    ("whitebullet", 0x25E6),
+    ("whitecircle", 0x25CB),
+    ("whitecircleinverse", 0x25D9),
+    ("whitediamond", 0x25C7),
+    ("whitediamondcontainingblacksmalldiamond", 0x25C8),
+    ("whitedownpointingsmalltriangle", 0x25BF),
+    ("whitedownpointingtriangle", 0x25BD),
+    ("whiteleftpointingsmalltriangle", 0x25C3),
+    ("whiteleftpointingtriangle", 0x25C1),
+    ("whiterightpointingsmalltriangle", 0x25B9),
+    ("whiterightpointingtriangle", 0x25B7),
+    ("whitesmallsquare", 0x25AB),
+    ("whitesquare", 0x25A1),
+    ("whiteuppointingsmalltriangle", 0x25B5),
+    ("whiteuppointingtriangle", 0x25B3),
+    ("won", 0x20A9),
+    ("wreathproduct", 0x2240),
+    ("wynn", 0x01BF),
+    ("x", 0x0078),
+    ("xi", 0x03BE),
+    ("y", 0x0079),
+    ("yacute", 0x00FD),
+    ("ycircumflex", 0x0177),
+    ("ydieresis", 0x00FF),
+    ("yen", 0x00A5),
+    ("yhook", 0x01B4),
+    ("yotgreek", 0x03F3),
+    ("ypogegrammeni", 0x037A),
+    ("yr", 0x01A6),
+    ("z", 0x007A),
+    ("zacute", 0x017A),
+    ("zcaron", 0x017E),
+    ("zdot", 0x017C),
+    ("zdotaccent", 0x017C),
+    ("zero", 0x0030),
+    ("zeroinferior", 0x2080),
+    ("zerosuperior", 0x2070),
+    ("zerowidthnonjoiner", 0x200C),
+    ("zerowidthspace", 0x200B),
+    ("zeta", 0x03B6),
+    ("zstroke", 0x01B6),
+];
+
+#[cfg(test)]
+mod glyph_test {
+    use super::*;
+    #[test]
+    fn glyph_to_unicode_test() {
+        assert_eq!(glyph_to_unicode("A"), Some("A".into()));
+        assert_eq!(glyph_to_unicode("endash"), Some("–".into()));
+        assert_eq!(glyph_to_unicode("fi"), Some("ﬁ".into()));
+        assert_eq!(glyph_to_unicode("a.sc"), Some("a".into()));
+        assert_eq!(glyph_to_unicode("f_f_i"), Some("ffi".into()));
+        assert_eq!(glyph_to_unicode("uni00410042"), Some("AB".into()));
+        assert_eq!(glyph_to_unicode("u1D400"), Some("𝐀".into()));
+        assert_eq!(glyph_to_unicode("uniabcd"), None);
+        assert_eq!(glyph_to_unicode(".notdef"), None);
+        assert!(GLYPHS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+}
@@ -0,0 +1,186 @@
+// runs whose baselines are closer than this share of the font size belong to the same line
+const LINE_TOLERANCE_RATIO: f64 = 0.5;
+// runs turned by less than this many degrees are read as upright
+const ANGLE_TOLERANCE: f64 = 1.0;
+const SPACE: char = ' ';
+
+/// A piece of text shown by a single text operator, positioned in user space
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextRun {
+    pub text: String,
+    /// start of the baseline
+    pub x: f64,
+    pub y: f64,
+    /// end of the baseline after the last glyph
+    pub end_x: f64,
+    pub end_y: f64,
+    /// font size scaled by the text matrix
+    pub size: f64,
+    /// horizontal gap after this run which is read as a word break
+    pub space_gap: f64,
+    /// counterclockwise angle of the baseline in degrees, in (-180, 180]
+    pub angle: f64,
+    /// false for text in render mode 3 or 7, painted white, or clipped away
+    pub visible: bool,
+}
+
+impl TextRun {
+    pub fn is_upright(&self) -> bool {
+        self.angle.abs() <= ANGLE_TOLERANCE
+    }
+
+    /// the run in a frame turned by angle, where its baseline runs along the x axis
+    fn unrotate(&self, angle: f64) -> TextRun {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        TextRun {
+            x: self.x * cos + self.y * sin,
+            y: self.y * cos - self.x * sin,
+            end_x: self.end_x * cos + self.end_y * sin,
+            end_y: self.end_y * cos - self.end_x * sin,
+            angle: self.angle - angle,
+            ..self.clone()
+        }
+    }
+}
+
+/// group text runs into visual lines by baseline and order each line from left to right, lines
+/// are returned from the top of the page to the bottom
+pub fn build_lines(runs: &[TextRun]) -> Vec<Vec<&TextRun>> {
+    let mut sorted = runs.iter().collect::<Vec<_>>();
+    sorted.sort_by(|a, b| b.y.total_cmp(&a.y).then(a.x.total_cmp(&b.x)));
+    let mut lines: Vec<(f64, f64, Vec<&TextRun>)> = vec![];
+    for run in sorted {
+        if let Some((y, size, line)) = lines.last_mut() {
+            let tolerance = LINE_TOLERANCE_RATIO * size.min(run.size).max(f64::EPSILON);
+            if (*y - run.y).abs() <= tolerance {
+                line.push(run);
+                *size = size.max(run.size);
+                continue;
+            }
+        }
+        lines.push((run.y, run.size, vec![run]));
+    }
+    lines
+        .into_iter()
+        .map(|(_, _, mut line)| {
+            line.sort_by(|a, b| a.x.total_cmp(&b.x));
+            line
+        })
+        .collect()
+}
+
+/// join the runs of a line, a space is put where the gap between runs is wide enough
+pub fn join_line(line: &[&TextRun]) -> String {
+    let mut row = String::new();
+    let mut previous: Option<&TextRun> = None;
+    for run in line {
+        if let Some(previous) = previous {
+            let gap = run.x - previous.end_x;
+            if gap > previous.space_gap
+                && !row.ends_with(char::is_whitespace)
+                && !run.text.starts_with(char::is_whitespace)
+            {
+                row.push(SPACE);
+            }
+        }
+        row.push_str(&run.text);
+        previous = Some(run);
+    }
+    row
+}
+
+/// the rows of a page in reading order, hidden runs are left out as well as runs which are not
+/// upright unless include_rotated is set, rotated rows then follow the upright ones
+pub fn build_rows(runs: &[TextRun], include_rotated: bool) -> Vec<String> {
+    let upright = runs
+        .iter()
+        .filter(|run| run.visible && run.is_upright())
+        .cloned()
+        .collect::<Vec<_>>();
+    let mut rows = build_lines(&upright)
+        .iter()
+        .map(|line| join_line(line))
+        .collect::<Vec<_>>();
+    if include_rotated {
+        rows.extend(build_rotated_rows(runs).into_iter().map(|(_, row)| row));
+    }
+    rows
+}
+
+/// the lines of rotated text such as the labels of a y axis, grouped by angle and read along
+/// their own baseline
+pub fn build_rotated_rows(runs: &[TextRun]) -> Vec<(f64, String)> {
+    let mut groups: Vec<(f64, Vec<TextRun>)> = vec![];
+    for run in runs.iter().filter(|run| run.visible && !run.is_upright()) {
+        match groups
+            .iter_mut()
+            .find(|(angle, _)| (angle - run.angle).abs() <= ANGLE_TOLERANCE)
+        {
+            Some((angle, group)) => group.push(run.unrotate(*angle)),
+            None => groups.push((run.angle, vec![run.unrotate(run.angle)])),
+        }
+    }
+    groups.sort_by(|a, b| a.0.total_cmp(&b.0));
+    groups
+        .iter()
+        .flat_map(|(angle, group)| {
+            build_lines(group)
+                .iter()
+                .map(|line| (*angle, join_line(line)))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod layout_test {
+    use super::*;
+    use crate::pdf::reader::test_util::run;
+
+    #[test]
+    fn build_rows_test() {
+        // cells drawn column by column, the second row sits slightly off the baseline
+        let runs = vec![
+            run("Subject", 72f64, 700f64),
+            run("01001", 72f64, 686f64),
+            run("Age", 150f64, 700f64),
+            run("45", 150f64, 685.2f64),
+            run("AKESO", 72f64, 750f64),
+            run("Inc", 102f64, 750f64),
+            run("(cont.)", 117f64, 750f64),
+        ];
+        let rows = build_rows(&runs, false);
+        assert_eq!(rows, vec!["AKESO Inc(cont.)", "Subject Age", "01001 45"]);
+    }
+
+    #[test]
+    fn build_rotated_rows_test() {
+        // a y axis label written one glyph at a time bottom up, next to an upright tick label
+        let glyph = |text: &str, y: f64| TextRun {
+            end_x: 40f64,
+            end_y: y + 5f64,
+            angle: 90f64,
+            ..run(text, 40f64, y)
+        };
+        let runs = vec![
+            glyph("A", 300f64),
+            glyph("g", 305f64),
+            glyph("e", 310f64),
+            glyph("(", 320f64),
+            glyph("y", 325f64),
+            glyph(")", 330f64),
+            run("20", 60f64, 300f64),
+            TextRun {
+                visible: false,
+                ..run("hidden", 60f64, 290f64)
+            },
+        ];
+        assert_eq!(build_rows(&runs, false), vec!["20"]);
+        assert_eq!(
+            build_rotated_rows(&runs),
+            vec![(90f64, "Age (y)".to_string())]
+        );
+        // the label is read as one row of its own rather than glyphs scattered over the rows
+        assert_eq!(build_rows(&runs, true), vec!["20", "Age (y)"]);
+    }
+}
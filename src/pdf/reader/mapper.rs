@@ -1,102 +1,347 @@
 use std::collections::HashMap;
 
-const BEGINBFCHAR: &[u8] = "beginbfchar".as_bytes();
-const ENDBFCHAR: &[u8] = "endbfchar".as_bytes();
-const BEGINBFRANGE: &[u8] = "beginbfrange".as_bytes();
-const ENDBFRANGE: &[u8] = "endbfrange".as_bytes();
-const MAP_ITEM_LEN: usize = "<0000> <0000>".len();
-const RANGE_MAP_ITEM_LEN: usize = "<0000> <0000> <0000>".len();
-const GREATER: u8 = b'>';
-const LESS: u8 = b'<';
-
-pub fn build_unicode_map(source: &[u8]) -> Option<HashMap<String, String>> {
-    let mut map: HashMap<String, String> = HashMap::new();
-    let mut range_indexes = vec![];
-    let mut char_indexes = vec![];
-    let mut index = (0, 0);
-
-    source.iter().enumerate().for_each(|(i, _)| {
-        if i > BEGINBFRANGE.len() {
-            if BEGINBFRANGE.eq(source.get(i - BEGINBFRANGE.len() + 1..i + 1).unwrap()) {
-                index.0 = i + 2;
+const BEGINCODESPACERANGE: &str = "begincodespacerange";
+const ENDCODESPACERANGE: &str = "endcodespacerange";
+const BEGINBFCHAR: &str = "beginbfchar";
+const ENDBFCHAR: &str = "endbfchar";
+const BEGINBFRANGE: &str = "beginbfrange";
+const ENDBFRANGE: &str = "endbfrange";
+// codes in a cmap are at most 4 bytes long
+const MAX_CODE_LEN: usize = 4;
+// ranges wider than this are treated as broken instead of being expanded
+const MAX_RANGE_SIZE: u32 = 0xFFFF;
+
+/// A parsed ToUnicode CMap, maps character codes of a font to unicode text
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct UnicodeMap {
+    codespaces: Vec<(Vec<u8>, Vec<u8>)>,
+    map: HashMap<Vec<u8>, String>,
+}
+
+impl UnicodeMap {
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// text mapped from a single character code
+    pub fn get(&self, code: &[u8]) -> Option<&str> {
+        self.map.get(code).map(|s| s.as_str())
+    }
+
+    /// inherit mappings and codespaces from the parent cmap, entries defined here take precedence
+    pub fn merge_parent(&mut self, parent: &UnicodeMap) {
+        for (code, text) in parent.map.iter() {
+            self.map.entry(code.clone()).or_insert(text.clone());
+        }
+        for range in parent.codespaces.iter() {
+            if !self.codespaces.contains(range) {
+                self.codespaces.push(range.clone());
+            }
+        }
+    }
+
+    /// split a string of the font into character codes following the codespace ranges
+    pub fn codes<'a>(&self, source: &'a [u8]) -> Vec<&'a [u8]> {
+        let mut codes = vec![];
+        let mut i = 0;
+        while i < source.len() {
+            let len = self.code_len(&source[i..]);
+            let end = (i + len).min(source.len());
+            codes.push(&source[i..end]);
+            i = end;
+        }
+        codes
+    }
+
+    fn code_len(&self, source: &[u8]) -> usize {
+        if self.codespaces.is_empty() {
+            // without codespace ranges fall back to the lengths used by the mapping itself
+            let mut lens = self.map.keys().map(|k| k.len()).collect::<Vec<_>>();
+            lens.sort();
+            lens.dedup();
+            for len in lens.iter() {
+                if let Some(code) = source.get(..*len) {
+                    if self.map.contains_key(code) {
+                        return *len;
+                    }
+                }
             }
-            if ENDBFRANGE.eq(source.get(i - ENDBFRANGE.len() + 1..i + 1).unwrap()) {
-                index.1 = i;
-                range_indexes.push(index)
+            return lens.last().copied().unwrap_or(2);
+        }
+        for len in 1..=MAX_CODE_LEN {
+            if let Some(code) = source.get(..len) {
+                let matched = self.codespaces.iter().any(|(low, high)| {
+                    low.len() == len
+                        && code
+                            .iter()
+                            .enumerate()
+                            .all(|(i, b)| low[i] <= *b && *b <= high[i])
+                });
+                if matched {
+                    return len;
+                }
+            }
+        }
+        // invalid code, skip as many bytes as the shortest codespace
+        self.codespaces
+            .iter()
+            .map(|(low, _)| low.len())
+            .min()
+            .unwrap_or(1)
+            .max(1)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Token {
+    Hex(Vec<u8>),
+    Keyword(String),
+    ArrayStart,
+    ArrayEnd,
+    Other,
+}
+
+/// parse a ToUnicode cmap stream, a cmap pulled in by `usecmap` is resolved by the reader through
+/// the /UseCMap entry of the stream and merged with [`UnicodeMap::merge_parent`]
+pub fn build_unicode_map(source: &[u8]) -> Option<UnicodeMap> {
+    let tokens = tokenize(source);
+    let mut unicode_map = UnicodeMap::default();
+    let mut i = 0;
+    while let Some(token) = tokens.get(i) {
+        i += 1;
+        let keyword = match token {
+            Token::Keyword(keyword) => keyword.as_str(),
+            _ => continue,
+        };
+        match keyword {
+            BEGINCODESPACERANGE => {
+                while let (Some(Token::Hex(low)), Some(Token::Hex(high))) =
+                    (tokens.get(i), tokens.get(i + 1))
+                {
+                    if low.len() == high.len() && !low.is_empty() && low.len() <= MAX_CODE_LEN {
+                        unicode_map.codespaces.push((low.clone(), high.clone()));
+                    }
+                    i += 2;
+                }
+                skip_to(&tokens, &mut i, ENDCODESPACERANGE);
             }
-            if BEGINBFCHAR.eq(source.get(i - BEGINBFCHAR.len() + 1..i + 1).unwrap()) {
-                index.0 = i + 2;
+            BEGINBFCHAR => {
+                while let (Some(Token::Hex(code)), Some(dest)) = (tokens.get(i), tokens.get(i + 1))
+                {
+                    if let Token::Hex(dest) = dest {
+                        unicode_map.map.insert(code.clone(), utf16_to_string(dest));
+                    }
+                    i += 2;
+                }
+                skip_to(&tokens, &mut i, ENDBFCHAR);
             }
-            if ENDBFCHAR.eq(source.get(i - ENDBFCHAR.len() + 1..i + 1).unwrap()) {
-                index.1 = i;
-                char_indexes.push(index)
+            BEGINBFRANGE => {
+                while let (Some(Token::Hex(low)), Some(Token::Hex(high))) =
+                    (tokens.get(i), tokens.get(i + 1))
+                {
+                    i += 2;
+                    match tokens.get(i) {
+                        Some(Token::Hex(dest)) => {
+                            insert_range(&mut unicode_map.map, low, high, |offset| {
+                                Some(increase_utf16(dest, offset))
+                            });
+                            i += 1;
+                        }
+                        Some(Token::ArrayStart) => {
+                            i += 1;
+                            let mut dests = vec![];
+                            while let Some(token) = tokens.get(i) {
+                                i += 1;
+                                match token {
+                                    Token::Hex(dest) => dests.push(utf16_to_string(dest)),
+                                    Token::ArrayEnd => break,
+                                    _ => {}
+                                }
+                            }
+                            insert_range(&mut unicode_map.map, low, high, |offset| {
+                                dests.get(offset as usize).cloned()
+                            });
+                        }
+                        _ => break,
+                    }
+                }
+                skip_to(&tokens, &mut i, ENDBFRANGE);
             }
+            _ => {}
         }
-    });
+    }
+    Some(unicode_map)
+}
 
-    range_indexes.iter().for_each(|(start, end)| {
-        let pairs = source.get(*start..*end).unwrap();
-        let mut i = 0;
-        while let Some(item) = pairs.get(i..i + RANGE_MAP_ITEM_LEN) {
-            unicode_map_pair(item).iter().for_each(|(key, value)| {
-                map.insert(key.to_owned(), value.to_owned());
-            });
-            i += RANGE_MAP_ITEM_LEN + 1;
+fn skip_to(tokens: &[Token], i: &mut usize, keyword: &str) {
+    while let Some(token) = tokens.get(*i) {
+        *i += 1;
+        if let Token::Keyword(k) = token {
+            if k.eq(keyword) {
+                return;
+            }
         }
-    });
-    char_indexes.iter().for_each(|(start, end)| {
-        let pairs = source.get(*start..*end).unwrap();
-        let mut i = 0;
-        while let Some(item) = pairs.get(i..i + MAP_ITEM_LEN) {
-            unicode_map_pair(item).iter().for_each(|(key, value)| {
-                map.insert(key.to_owned(), value.to_owned());
-            });
-            i += MAP_ITEM_LEN + 1;
+    }
+}
+
+fn insert_range<F>(map: &mut HashMap<Vec<u8>, String>, low: &[u8], high: &[u8], dest: F)
+where
+    F: Fn(u32) -> Option<String>,
+{
+    if low.len() != high.len() || low.is_empty() || low.len() > MAX_CODE_LEN {
+        return;
+    }
+    let start = bytes_to_u32(low);
+    let end = bytes_to_u32(high);
+    if end < start || end - start > MAX_RANGE_SIZE {
+        return;
+    }
+    for code in start..=end {
+        if let Some(text) = dest(code - start) {
+            let key = code.to_be_bytes()[4 - low.len()..].to_vec();
+            map.insert(key, text);
         }
-    });
+    }
+}
+
+fn bytes_to_u32(source: &[u8]) -> u32 {
+    source.iter().fold(0, |acc, b| (acc << 8) | *b as u32)
+}
+
+/// the destination of a bfrange is incremented on its last utf-16 unit for every code in the range
+fn increase_utf16(dest: &[u8], offset: u32) -> String {
+    let mut units = utf16_units(dest);
+    if let Some(last) = units.last_mut() {
+        *last = last.wrapping_add(offset as u16);
+    }
+    String::from_utf16_lossy(&units)
+}
+
+fn utf16_units(source: &[u8]) -> Vec<u16> {
+    source
+        .chunks(2)
+        .map(|pair| match pair {
+            [high, low] => u16::from_be_bytes([*high, *low]),
+            [single] => *single as u16,
+            _ => 0,
+        })
+        .collect()
+}
+
+fn utf16_to_string(source: &[u8]) -> String {
+    String::from_utf16_lossy(&utf16_units(source))
+}
+
+/// convert the hex digits of a hex string into bytes, whitespace is ignored and an odd trailing
+/// digit is padded with zero
+pub fn hex_to_bytes(source: &[u8]) -> Vec<u8> {
+    let digits = source
+        .iter()
+        .filter_map(|c| (*c as char).to_digit(16))
+        .map(|d| d as u8)
+        .collect::<Vec<_>>();
+    digits
+        .chunks(2)
+        .map(|pair| (pair[0] << 4) | pair.get(1).copied().unwrap_or(0))
+        .collect()
+}
 
-    Some(map)
+fn is_delimiter(c: u8) -> bool {
+    matches!(
+        c,
+        b'(' | b')' | b'<' | b'>' | b'[' | b']' | b'{' | b'}' | b'/' | b'%'
+    )
 }
 
-fn unicode_map_pair(source: &[u8]) -> Vec<(String, String)> {
-    let mut pairs = vec![];
-    match source.len() {
-        13 => {
-            if source.first().unwrap().eq(&LESS) && source.last().unwrap().eq(&GREATER) {
-                pairs.push((
-                    String::from_utf8_lossy(source.get(1..5).unwrap()).to_string(),
-                    String::from_utf8_lossy(source.get(8..12).unwrap()).to_string(),
-                ));
+fn tokenize(source: &[u8]) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut i = 0;
+    while let Some(c) = source.get(i) {
+        match c {
+            c if c.is_ascii_whitespace() || *c == 0 => i += 1,
+            b'%' => {
+                while let Some(c) = source.get(i) {
+                    if *c == b'\n' || *c == b'\r' {
+                        break;
+                    }
+                    i += 1;
+                }
             }
-        }
-        20 => {
-            if source.first().unwrap().eq(&LESS) && source.last().unwrap().eq(&GREATER) {
-                let start = usize::from_str_radix(
-                    String::from_utf8_lossy(source.get(1..5).unwrap())
-                        .to_string()
-                        .as_str(),
-                    16,
-                );
-                let end = usize::from_str_radix(
-                    String::from_utf8_lossy(source.get(8..12).unwrap())
-                        .to_string()
-                        .as_str(),
-                    16,
-                );
-                if start.is_ok() && end.is_ok() {
-                    for i in start.unwrap()..=end.unwrap() {
-                        pairs.push((
-                            format!("{:04X}", i),
-                            String::from_utf8_lossy(source.get(15..19).unwrap()).to_string(),
-                        ))
+            b'<' if source.get(i + 1) == Some(&b'<') => {
+                tokens.push(Token::Other);
+                i += 2;
+            }
+            b'>' if source.get(i + 1) == Some(&b'>') => {
+                tokens.push(Token::Other);
+                i += 2;
+            }
+            b'<' => {
+                let start = i + 1;
+                let end = source[start..]
+                    .iter()
+                    .position(|c| *c == b'>')
+                    .map(|p| start + p)
+                    .unwrap_or(source.len());
+                tokens.push(Token::Hex(hex_to_bytes(&source[start..end])));
+                i = end + 1;
+            }
+            b'[' => {
+                tokens.push(Token::ArrayStart);
+                i += 1;
+            }
+            b']' => {
+                tokens.push(Token::ArrayEnd);
+                i += 1;
+            }
+            b'(' => {
+                // literal strings only show up in the cmap header, their content is not needed
+                let mut depth = 0;
+                while let Some(c) = source.get(i) {
+                    match c {
+                        b'\\' => i += 1,
+                        b'(' => depth += 1,
+                        b')' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                i += 1;
+                                break;
+                            }
+                        }
+                        _ => {}
                     }
+                    i += 1;
+                }
+                tokens.push(Token::Other);
+            }
+            _ => {
+                let start = if *c == b'/' { i + 1 } else { i };
+                let mut end = start;
+                while let Some(c) = source.get(end) {
+                    if c.is_ascii_whitespace() || is_delimiter(*c) {
+                        break;
+                    }
+                    end += 1;
+                }
+                let word = String::from_utf8_lossy(&source[start..end]).to_string();
+                if *c == b'/' || word.parse::<f64>().is_ok() {
+                    tokens.push(Token::Other);
+                } else if word.is_empty() {
+                    // a stray delimiter such as '>', '{' or '}'
+                    tokens.push(Token::Other);
+                    end += 1;
+                } else {
+                    tokens.push(Token::Keyword(word));
                 }
+                i = end;
             }
         }
-        _ => {}
     }
-
-    pairs
+    tokens
 }
 
 #[cfg(test)]
@@ -104,6 +349,14 @@ mod mapper_test {
     use std::{fs, path::Path};
 
     use super::*;
+
+    fn decode(map: &UnicodeMap, source: &[u8]) -> String {
+        map.codes(source)
+            .into_iter()
+            .filter_map(|code| map.get(code))
+            .collect()
+    }
+
     #[test]
     fn build_unicode_map_test() {
         let ft = Path::new(r"D:\misc\utils\rtfs\data\FT9.txt");
@@ -118,12 +371,56 @@ mod mapper_test {
 
     #[test]
     fn unicode_map_pair_test() {
-        let source = r"<02C8> <FF0C>".as_bytes();
-        let result = unicode_map_pair(source);
+        let source = r"beginbfchar <02C8> <FF0C> endbfchar".as_bytes();
+        let result = build_unicode_map(source).unwrap();
         assert_eq!(result.len(), 1);
+        assert_eq!(result.get(&[0x02, 0xC8]), Some("，"));
 
-        let source = r"<02C4> <02C5> <FF08>".as_bytes();
-        let result = unicode_map_pair(source);
+        let source = r"beginbfrange <02C4> <02C5> <FF08> endbfrange".as_bytes();
+        let result = build_unicode_map(source).unwrap();
         assert_eq!(result.len(), 2);
+        assert_eq!(result.get(&[0x02, 0xC4]), Some("（"));
+        assert_eq!(result.get(&[0x02, 0xC5]), Some("）"));
+    }
+
+    #[test]
+    fn codespace_and_array_range_test() {
+        let source = r"/CIDInit /ProcSet findresource begin
+12 dict begin
+begincmap
+/CMapName /Adobe-Identity-UCS def
+2 begincodespacerange
+<00>   <80>
+<8140><FEFE>
+endcodespacerange
+1 beginbfchar
+<41><0041>
+endbfchar
+2 beginbfrange
+<8141> <8143> [<0066006C> <D835DC00> <0062>]
+<42><44><0062>
+endbfrange
+endcmap"
+            .as_bytes();
+        let map = build_unicode_map(source).unwrap();
+        assert_eq!(map.len(), 7);
+        assert_eq!(map.get(&[0x81, 0x41]), Some("fl"));
+        assert_eq!(map.get(&[0x81, 0x42]), Some("𝐀"));
+        assert_eq!(map.get(&[0x44]), Some("d"));
+        assert_eq!(
+            decode(&map, &[0x41, 0x81, 0x41, 0x42, 0x99, 0x50, 0x43]),
+            "Aflbc"
+        );
+    }
+
+    #[test]
+    fn usecmap_test() {
+        let parent = build_unicode_map(r"beginbfchar <0001> <0041> endbfchar".as_bytes()).unwrap();
+        let mut map =
+            build_unicode_map(r"/Parent usecmap 1 beginbfchar <0002> <0042> endbfchar".as_bytes())
+                .unwrap();
+        assert_eq!(map.len(), 1);
+        map.merge_parent(&parent);
+        assert_eq!(decode(&map, &[0x00, 0x01, 0x00, 0x02]), "AB");
     }
 }
@@ -0,0 +1,294 @@
+use super::{mapper::hex_to_bytes, tj::literal_string};
+
+const INLINE_IMAGE_BEGIN: &str = "BI";
+const INLINE_IMAGE_DATA: &str = "ID";
+const INLINE_IMAGE_END: &[u8] = b"EI";
+
+/// An operand of a content stream operator
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    Number(f64),
+    Name(String),
+    /// literal string with escapes resolved
+    Literal(Vec<u8>),
+    /// hex string converted to bytes
+    Hex(Vec<u8>),
+    Array(Vec<Operand>),
+    Dictionary(Vec<(String, Operand)>),
+    /// true, false and null
+    Keyword(String),
+}
+
+impl Operand {
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            Operand::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_name(&self) -> Option<&str> {
+        match self {
+            Operand::Name(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// the value of a key in a dictionary operand
+    pub fn get(&self, key: &str) -> Option<&Operand> {
+        match self {
+            Operand::Dictionary(pairs) => pairs.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+/// An operator with the operands preceding it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Operation {
+    pub operator: String,
+    pub operands: Vec<Operand>,
+}
+
+impl Operation {
+    /// the operand at index as number, missing operands read as zero
+    pub fn number(&self, index: usize) -> f64 {
+        self.operands
+            .get(index)
+            .and_then(Operand::as_number)
+            .unwrap_or(0f64)
+    }
+}
+
+fn is_delimiter(c: u8) -> bool {
+    matches!(
+        c,
+        b'(' | b')' | b'<' | b'>' | b'[' | b']' | b'{' | b'}' | b'/' | b'%'
+    )
+}
+
+fn is_whitespace(c: u8) -> bool {
+    c.is_ascii_whitespace() || c == 0
+}
+
+enum Token {
+    Operand(Operand),
+    ArrayEnd,
+    DictionaryEnd,
+    Operator(String),
+}
+
+struct Lexer<'a> {
+    source: &'a [u8],
+    i: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn next_token(&mut self) -> Option<Token> {
+        loop {
+            let c = *self.source.get(self.i)?;
+            match c {
+                c if is_whitespace(c) => self.i += 1,
+                b'%' => {
+                    while let Some(c) = self.source.get(self.i) {
+                        if *c == b'\n' || *c == b'\r' {
+                            break;
+                        }
+                        self.i += 1;
+                    }
+                }
+                b'(' => {
+                    let (bytes, end) = literal_string(self.source, self.i);
+                    self.i = end;
+                    return Some(Token::Operand(Operand::Literal(bytes)));
+                }
+                b'<' if self.source.get(self.i + 1) == Some(&b'<') => {
+                    self.i += 2;
+                    return Some(Token::Operand(self.dictionary()));
+                }
+                b'>' if self.source.get(self.i + 1) == Some(&b'>') => {
+                    self.i += 2;
+                    return Some(Token::DictionaryEnd);
+                }
+                b'<' => {
+                    let start = self.i + 1;
+                    let end = self.source[start..]
+                        .iter()
+                        .position(|c| *c == b'>')
+                        .map_or(self.source.len(), |p| start + p);
+                    self.i = end + 1;
+                    let bytes = hex_to_bytes(&self.source[start..end]);
+                    return Some(Token::Operand(Operand::Hex(bytes)));
+                }
+                b'[' => {
+                    self.i += 1;
+                    let mut items = vec![];
+                    loop {
+                        match self.next_token() {
+                            Some(Token::Operand(operand)) => items.push(operand),
+                            Some(Token::ArrayEnd) | None => break,
+                            // operators do not belong in arrays, keep going
+                            Some(_) => {}
+                        }
+                    }
+                    return Some(Token::Operand(Operand::Array(items)));
+                }
+                b']' => {
+                    self.i += 1;
+                    return Some(Token::ArrayEnd);
+                }
+                b'/' => {
+                    let word = self.word(self.i + 1);
+                    return Some(Token::Operand(Operand::Name(word)));
+                }
+                c if is_delimiter(c) => {
+                    // a stray delimiter such as ')', '>', '{' or '}'
+                    self.i += 1;
+                }
+                _ => {
+                    let word = self.word(self.i);
+                    if let Ok(n) = word.parse::<f64>() {
+                        return Some(Token::Operand(Operand::Number(n)));
+                    }
+                    return match word.as_str() {
+                        "true" | "false" | "null" => Some(Token::Operand(Operand::Keyword(word))),
+                        _ => Some(Token::Operator(word)),
+                    };
+                }
+            }
+        }
+    }
+
+    /// read a regular word starting at start, the lexer is moved behind it
+    fn word(&mut self, start: usize) -> String {
+        let mut end = start;
+        while let Some(c) = self.source.get(end) {
+            if is_whitespace(*c) || is_delimiter(*c) {
+                break;
+            }
+            end += 1;
+        }
+        self.i = end;
+        String::from_utf8_lossy(&self.source[start..end]).to_string()
+    }
+
+    /// read key value pairs until `>>`, or until `ID` for inline images
+    fn dictionary(&mut self) -> Operand {
+        let mut pairs = vec![];
+        let mut key: Option<String> = None;
+        loop {
+            match self.next_token() {
+                Some(Token::Operand(Operand::Name(name))) if key.is_none() => key = Some(name),
+                Some(Token::Operand(value)) => {
+                    if let Some(key) = key.take() {
+                        pairs.push((key, value));
+                    }
+                }
+                Some(Token::Operator(operator)) if operator.eq(INLINE_IMAGE_DATA) => break,
+                Some(Token::Operator(operator)) => {
+                    // inline image dictionaries may use abbreviated names such as /CS /RGB
+                    if let Some(key) = key.take() {
+                        pairs.push((key, Operand::Keyword(operator)));
+                    }
+                }
+                Some(Token::DictionaryEnd) | None => break,
+                Some(Token::ArrayEnd) => {}
+            }
+        }
+        Operand::Dictionary(pairs)
+    }
+
+    /// skip the binary data of an inline image, it ends with `EI` surrounded by whitespace
+    fn skip_inline_image(&mut self) {
+        // a single whitespace separates ID from the data
+        self.i += 1;
+        while self.i < self.source.len() {
+            let at_end = self.source.get(self.i..self.i + 2) == Some(INLINE_IMAGE_END)
+                && self.i > 0
+                && is_whitespace(self.source[self.i - 1])
+                && !matches!(self.source.get(self.i + 2), Some(c) if !is_whitespace(*c));
+            if at_end {
+                self.i += 2;
+                return;
+            }
+            self.i += 1;
+        }
+    }
+}
+
+/// split a content stream into operations
+pub fn parse_operations(source: &[u8]) -> Vec<Operation> {
+    let mut lexer = Lexer { source, i: 0 };
+    let mut operations = vec![];
+    let mut operands = vec![];
+    while let Some(token) = lexer.next_token() {
+        match token {
+            Token::Operand(operand) => operands.push(operand),
+            Token::Operator(operator) if operator.eq(INLINE_IMAGE_BEGIN) => {
+                // the inline image dictionary becomes the operand of BI
+                let dictionary = lexer.dictionary();
+                lexer.skip_inline_image();
+                operations.push(Operation {
+                    operator,
+                    operands: vec![dictionary],
+                });
+                operands.clear();
+            }
+            Token::Operator(operator) => operations.push(Operation {
+                operator,
+                operands: std::mem::take(&mut operands),
+            }),
+            Token::ArrayEnd | Token::DictionaryEnd => {}
+        }
+    }
+    operations
+}
+
+#[cfg(test)]
+mod operation_test {
+    use super::*;
+    #[test]
+    fn parse_operations_test() {
+        let source = "BT\r/FT9 10.5 Tf\r1 0 0 1 72 700.5 Tm\r[(p)-6(r\\))]TJ\r\
+<0026>Tj 139.188 -0 TD<0036>Tj\nET\n\
+/Span<</ActualText(x)>>BDC EMC\n\
+q BI /W 2 /H 1 /CS /G /BPC 8 ID \x00EI\x7f EI Q"
+            .as_bytes();
+        let operations = parse_operations(source);
+        let operators = operations
+            .iter()
+            .map(|o| o.operator.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            operators,
+            vec!["BT", "Tf", "Tm", "TJ", "Tj", "TD", "Tj", "ET", "BDC", "EMC", "q", "BI", "Q"]
+        );
+        assert_eq!(
+            operations[1].operands,
+            vec![Operand::Name("FT9".into()), Operand::Number(10.5)]
+        );
+        assert_eq!(operations[2].number(5), 700.5);
+        assert_eq!(
+            operations[3].operands,
+            vec![Operand::Array(vec![
+                Operand::Literal(b"p".to_vec()),
+                Operand::Number(-6f64),
+                Operand::Literal(b"r)".to_vec()),
+            ])]
+        );
+        assert_eq!(operations[4].operands, vec![Operand::Hex(vec![0x00, 0x26])]);
+        assert_eq!(
+            operations[8].operands[1],
+            Operand::Dictionary(vec![("ActualText".into(), Operand::Literal(b"x".to_vec()))])
+        );
+        assert_eq!(
+            operations[11].operands,
+            vec![Operand::Dictionary(vec![
+                ("W".into(), Operand::Number(2f64)),
+                ("H".into(), Operand::Number(1f64)),
+                ("CS".into(), Operand::Name("G".into())),
+                ("BPC".into(), Operand::Number(8f64)),
+            ])]
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use regex::Regex;
+
+use super::{
+    layout::{self, TextRun},
+    path::{self, Rule, Segment},
+    table::{self, Cell},
+    tm::{self, Matrix},
+};
+
+// us letter, the default media box
+const DEFAULT_BOX: [f64; 4] = [0f64, 0f64, 612f64, 792f64];
+
+/// The visible area and orientation of a page
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Geometry {
+    /// crop box clipped to the media box as `[llx lly urx ury]` in user space
+    pub bbox: [f64; 4],
+    /// clockwise rotation of the page when displayed, one of 0, 90, 180 and 270
+    pub rotate: u16,
+}
+
+impl Default for Geometry {
+    fn default() -> Self {
+        Geometry {
+            bbox: DEFAULT_BOX,
+            rotate: 0,
+        }
+    }
+}
+
+impl Geometry {
+    pub fn new(media_box: Option<[f64; 4]>, crop_box: Option<[f64; 4]>, rotate: i64) -> Geometry {
+        let normalize = |b: [f64; 4]| {
+            [
+                b[0].min(b[2]),
+                b[1].min(b[3]),
+                b[0].max(b[2]),
+                b[1].max(b[3]),
+            ]
+        };
+        let media = normalize(media_box.unwrap_or(DEFAULT_BOX));
+        let bbox = match crop_box.map(normalize) {
+            Some(crop) => [
+                crop[0].max(media[0]),
+                crop[1].max(media[1]),
+                crop[2].min(media[2]),
+                crop[3].min(media[3]),
+            ],
+            None => media,
+        };
+        // a crop box outside the media box is ignored
+        let bbox = if bbox[0] < bbox[2] && bbox[1] < bbox[3] {
+            bbox
+        } else {
+            media
+        };
+        let rotate = (((rotate as f64 / 90f64).round() as i64 * 90).rem_euclid(360)) as u16;
+        Geometry { bbox, rotate }
+    }
+
+    /// width as displayed
+    pub fn width(&self) -> f64 {
+        match self.rotate {
+            90 | 270 => self.bbox[3] - self.bbox[1],
+            _ => self.bbox[2] - self.bbox[0],
+        }
+    }
+
+    /// height as displayed
+    pub fn height(&self) -> f64 {
+        match self.rotate {
+            90 | 270 => self.bbox[2] - self.bbox[0],
+            _ => self.bbox[3] - self.bbox[1],
+        }
+    }
+
+    /// the matrix from user space to the upright page, with the origin at its lower left corner
+    pub fn matrix(&self) -> Matrix {
+        let [llx, lly, urx, ury] = self.bbox;
+        match self.rotate {
+            90 => [0f64, -1f64, 1f64, 0f64, -lly, urx],
+            180 => [-1f64, 0f64, 0f64, -1f64, urx, ury],
+            270 => [0f64, 1f64, -1f64, 0f64, ury, -llx],
+            _ => [1f64, 0f64, 0f64, 1f64, -llx, -lly],
+        }
+    }
+}
+
+/// The text and graphics read from the content stream of a page, coordinates are given on the
+/// upright page
+#[derive(Debug, Clone, Default)]
+pub struct Page {
+    pub geometry: Geometry,
+    pub runs: Vec<TextRun>,
+    pub segments: Vec<Segment>,
+    pub images: Vec<Image>,
+}
+
+impl Page {
+    /// the visible text in reading order, rotated text is left out unless include_rotated is set
+    pub fn rows(&self, include_rotated: bool) -> Vec<String> {
+        layout::build_rows(&self.runs, include_rotated)
+    }
+
+    /// the rows split into cells placed in columns, with the header rows marked, rows holding
+    /// one of the page labels are never taken for the header
+    pub fn table(&self, page_labels: &[Regex]) -> Vec<Vec<Cell>> {
+        table::build_table(&self.runs, &self.rules(), page_labels)
+    }
+
+    /// horizontal rules from the top of the page down
+    pub fn rules(&self) -> Vec<Rule> {
+        path::build_rules(&self.segments)
+    }
+}
+
+/// An image painted on the page, the ctm maps the unit square onto its placement box
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    /// resource name of an image xobject, None for inline images
+    pub name: Option<String>,
+    /// size in samples
+    pub width: f64,
+    pub height: f64,
+    /// placement box in user space
+    pub x: f64,
+    pub y: f64,
+    pub end_x: f64,
+    pub end_y: f64,
+}
+
+impl Image {
+    pub fn new(name: Option<String>, width: f64, height: f64, ctm: &Matrix) -> Image {
+        let corners = [(0f64, 0f64), (1f64, 0f64), (0f64, 1f64), (1f64, 1f64)]
+            .map(|(x, y)| tm::multiply(&tm::translate(x, y), ctm));
+        let xs = corners.map(|m| m[4]);
+        let ys = corners.map(|m| m[5]);
+        Image {
+            name,
+            width,
+            height,
+            x: xs.into_iter().fold(f64::INFINITY, f64::min),
+            y: ys.into_iter().fold(f64::INFINITY, f64::min),
+            end_x: xs.into_iter().fold(f64::NEG_INFINITY, f64::max),
+            end_y: ys.into_iter().fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+}
+
+#[cfg(test)]
+mod page_test {
+    use super::*;
+
+    #[test]
+    fn geometry_test() {
+        let geometry = Geometry::new(
+            Some([0f64, 0f64, 612f64, 792f64]),
+            Some([10f64, 20f64, 600f64, 800f64]),
+            -270,
+        );
+        assert_eq!(geometry.bbox, [10f64, 20f64, 600f64, 792f64]);
+        assert_eq!(geometry.rotate, 90);
+        assert_eq!((geometry.width(), geometry.height()), (772f64, 590f64));
+        // the top left corner of the box is shown at the top right
+        let corner = tm::multiply(&tm::translate(10f64, 792f64), &geometry.matrix());
+        assert_eq!((corner[4], corner[5]), (772f64, 590f64));
+
+        let geometry = Geometry::new(None, Some([700f64, 0f64, 800f64, 10f64]), 0);
+        assert_eq!(geometry.bbox, [0f64, 0f64, 612f64, 792f64]);
+        assert_eq!((geometry.width(), geometry.height()), (612f64, 792f64));
+    }
+
+    #[test]
+    fn image_test() {
+        // an image flipped vertically as some writers place them
+        let image = Image::new(
+            None,
+            10f64,
+            20f64,
+            &[200f64, 0f64, 0f64, -100f64, 72f64, 600f64],
+        );
+        assert_eq!(
+            (image.x, image.y, image.end_x, image.end_y),
+            (72f64, 500f64, 272f64, 600f64)
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use super::{
+    operation::Operation,
+    tm::{self, Matrix},
+};
+
+// filled rectangles thinner than this are drawn rules rather than shaded areas
+const MAX_RULE_WIDTH: f64 = 2.0;
+// segments whose ends differ less than this are read as horizontal or vertical
+const ALIGN_TOLERANCE: f64 = 0.5;
+// pieces of a rule drawn cell by cell may leave gaps up to this wide
+const RULE_GAP: f64 = 1.0;
+
+pub type Point = (f64, f64);
+
+/// A straight line painted on the page, in user space
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+    /// line width, or the thickness of a filled rectangle
+    pub width: f64,
+    /// painted by a fill operator rather than a stroke
+    pub filled: bool,
+}
+
+impl Segment {
+    pub fn is_horizontal(&self) -> bool {
+        (self.start.1 - self.end.1).abs() <= ALIGN_TOLERANCE
+    }
+}
+
+/// A horizontal rule assembled from the segments drawn at the same height
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    pub x: f64,
+    pub end_x: f64,
+    pub y: f64,
+}
+
+/// Path under construction, points are transformed by the ctm as they are added
+#[derive(Default)]
+pub struct PathBuilder {
+    lines: Vec<(Point, Point)>,
+    /// rectangles given by two opposite corners
+    rects: Vec<(Point, Point)>,
+    current: Option<Point>,
+    subpath_start: Option<Point>,
+}
+
+impl PathBuilder {
+    /// apply a path construction operator, returns false for any other operator
+    pub fn apply(&mut self, operation: &Operation, ctm: &Matrix) -> bool {
+        let point = |x: f64, y: f64| transform(ctm, x, y);
+        match operation.operator.as_str() {
+            "m" => {
+                let p = point(operation.number(0), operation.number(1));
+                self.current = Some(p);
+                self.subpath_start = Some(p);
+            }
+            "l" => {
+                let p = point(operation.number(0), operation.number(1));
+                if let Some(current) = self.current {
+                    self.lines.push((current, p));
+                }
+                self.current = Some(p);
+            }
+            // curves are no rules, only the current point moves
+            "c" => self.current = Some(point(operation.number(4), operation.number(5))),
+            "v" | "y" => self.current = Some(point(operation.number(2), operation.number(3))),
+            "h" => self.close(),
+            "re" => {
+                let (x, y) = (operation.number(0), operation.number(1));
+                let (w, h) = (operation.number(2), operation.number(3));
+                let corners = [
+                    point(x, y),
+                    point(x + w, y),
+                    point(x + w, y + h),
+                    point(x, y + h),
+                ];
+                for i in 0..4 {
+                    self.lines.push((corners[i], corners[(i + 1) % 4]));
+                }
+                self.rects.push((corners[0], corners[2]));
+                self.current = Some(corners[0]);
+                self.subpath_start = Some(corners[0]);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// bounds of the path as `[llx lly urx ury]`
+    pub fn bounds(&self) -> Option<[f64; 4]> {
+        let mut points = self.lines.iter().flat_map(|(start, end)| [start, end]);
+        let first = points.next()?;
+        Some(points.fold([first.0, first.1, first.0, first.1], |b, p| {
+            [b[0].min(p.0), b[1].min(p.1), b[2].max(p.0), b[3].max(p.1)]
+        }))
+    }
+
+    fn close(&mut self) {
+        if let (Some(current), Some(start)) = (self.current, self.subpath_start) {
+            if current != start {
+                self.lines.push((current, start));
+            }
+            self.current = Some(start);
+        }
+    }
+
+    /// apply a path painting operator, returns the painted segments and whether the operator
+    /// was one, the path is cleared afterwards
+    pub fn paint(&mut self, operator: &str, line_width: f64) -> Option<Vec<Segment>> {
+        let (stroke, fill) = match operator {
+            "S" => (true, false),
+            "s" => {
+                self.close();
+                (true, false)
+            }
+            "f" | "F" | "f*" => (false, true),
+            "B" | "B*" => (true, true),
+            "b" | "b*" => {
+                self.close();
+                (true, true)
+            }
+            "n" => (false, false),
+            _ => return None,
+        };
+        let mut segments = vec![];
+        if stroke {
+            segments.extend(self.lines.iter().map(|(start, end)| Segment {
+                start: *start,
+                end: *end,
+                width: line_width,
+                filled: false,
+            }));
+        }
+        if fill {
+            segments.extend(self.rects.iter().filter_map(thin_rect));
+        }
+        *self = PathBuilder::default();
+        Some(segments)
+    }
+}
+
+fn transform(m: &Matrix, x: f64, y: f64) -> Point {
+    let p = tm::multiply(&tm::translate(x, y), m);
+    (p[4], p[5])
+}
+
+/// the center line of a filled rectangle thin enough to be a rule
+fn thin_rect(((x1, y1), (x2, y2)): &(Point, Point)) -> Option<Segment> {
+    let (left, right) = (x1.min(*x2), x1.max(*x2));
+    let (bottom, top) = (y1.min(*y2), y1.max(*y2));
+    let (width, height) = (right - left, top - bottom);
+    if height <= MAX_RULE_WIDTH && width > height {
+        let y = (bottom + top) / 2f64;
+        Some(Segment {
+            start: (left, y),
+            end: (right, y),
+            width: height,
+            filled: true,
+        })
+    } else if width <= MAX_RULE_WIDTH && height > width {
+        let x = (left + right) / 2f64;
+        Some(Segment {
+            start: (x, bottom),
+            end: (x, top),
+            width,
+            filled: true,
+        })
+    } else {
+        None
+    }
+}
+
+/// join the horizontal segments of a page into rules, ordered from the top of the page down
+pub fn build_rules(segments: &[Segment]) -> Vec<Rule> {
+    let mut pieces = segments
+        .iter()
+        .filter(|segment| segment.is_horizontal())
+        .map(|segment| Rule {
+            x: segment.start.0.min(segment.end.0),
+            end_x: segment.start.0.max(segment.end.0),
+            y: (segment.start.1 + segment.end.1) / 2f64,
+        })
+        .collect::<Vec<_>>();
+    pieces.sort_by(|a, b| b.y.total_cmp(&a.y).then(a.x.total_cmp(&b.x)));
+    let mut rules: Vec<Rule> = vec![];
+    for piece in pieces {
+        if let Some(rule) = rules.iter_mut().rev().find(|rule| {
+            (rule.y - piece.y).abs() <= ALIGN_TOLERANCE && piece.x <= rule.end_x + RULE_GAP
+        }) {
+            rule.end_x = rule.end_x.max(piece.end_x);
+            continue;
+        }
+        rules.push(piece);
+    }
+    rules
+}
+
+#[cfg(test)]
+mod path_test {
+    use super::super::operation::parse_operations;
+    use super::*;
+
+    fn paint(source: &str) -> Vec<Segment> {
+        let mut path = PathBuilder::default();
+        let ctm = [1f64, 0f64, 0f64, 1f64, 0f64, 10f64];
+        let mut segments = vec![];
+        for operation in parse_operations(source.as_bytes()) {
+            if !path.apply(&operation, &ctm) {
+                segments.extend(path.paint(&operation.operator, 0.5).unwrap_or_default());
+            }
+        }
+        segments
+    }
+
+    #[test]
+    fn paint_test() {
+        let segments = paint("72 700 m 300 700 l S 72 690 m 100 690 l n");
+        assert_eq!(
+            segments,
+            vec![Segment {
+                start: (72f64, 710f64),
+                end: (300f64, 710f64),
+                width: 0.5,
+                filled: false,
+            }]
+        );
+        assert!(segments[0].is_horizontal());
+
+        // a rule drawn as a filled rectangle, a shaded box is no rule
+        let segments = paint("72 600 228 0.75 re f 72 400 100 50 re f");
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].start, (72f64, 610.375));
+        assert_eq!(segments[0].width, 0.75);
+
+        // a stroked cell frame
+        let segments = paint("10 10 20 5 re S");
+        assert_eq!(segments.iter().filter(|s| !s.is_horizontal()).count(), 2);
+    }
+
+    #[test]
+    fn build_rules_test() {
+        let segments = paint(
+            "72 700 m 150 700 l 150.5 700 m 300 700 l S 72 500 m 300 500.25 l S 72 650 m 72 500 l S",
+        );
+        let rules = build_rules(&segments);
+        assert_eq!(
+            rules,
+            vec![
+                Rule {
+                    x: 72f64,
+                    end_x: 300f64,
+                    y: 710f64
+                },
+                Rule {
+                    x: 72f64,
+                    end_x: 300f64,
+                    y: 510.125
+                },
+            ]
+        );
+    }
+}
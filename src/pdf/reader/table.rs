@@ -0,0 +1,365 @@
+use regex::Regex;
+
+use super::{
+    layout::{self, TextRun},
+    path::Rule,
+};
+
+// runs further apart than this share of the font size belong to different cells
+const CELL_GAP_RATIO: f64 = 1.0;
+// a rule shorter than this share of the table width underlines a label only
+const RULE_SPAN_RATIO: f64 = 0.5;
+
+/// A table cell rebuilt from the text runs of a line
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cell {
+    pub text: String,
+    pub x: f64,
+    pub end_x: f64,
+    /// baseline of the line holding the cell
+    pub y: f64,
+    /// index of the first column covered by the cell
+    pub column: usize,
+    /// number of columns covered, spanning headers and titles cover more than one
+    pub span: usize,
+    pub header: bool,
+}
+
+impl Cell {
+    fn overlaps(&self, start: f64, end: f64) -> bool {
+        self.x < end && start < self.end_x
+    }
+}
+
+/// split a line into cells where the gap between runs is wider than the cell gap
+fn split_cells(line: &[&TextRun]) -> Vec<Cell> {
+    let mut groups: Vec<Vec<&TextRun>> = vec![];
+    for run in line {
+        match groups.last_mut() {
+            Some(group)
+                if group.last().is_some_and(|previous| {
+                    run.x - previous.end_x <= CELL_GAP_RATIO * previous.size.max(run.size)
+                }) =>
+            {
+                group.push(run)
+            }
+            _ => groups.push(vec![run]),
+        }
+    }
+    groups
+        .iter()
+        .map(|group| Cell {
+            text: layout::join_line(group).trim().to_string(),
+            x: group[0].x,
+            end_x: group.iter().map(|run| run.end_x).fold(group[0].x, f64::max),
+            y: group[0].y,
+            column: 0,
+            span: 1,
+            header: false,
+        })
+        .filter(|cell| !cell.text.is_empty())
+        .collect()
+}
+
+/// column extents from the lines holding more than one cell, cells whose extents overlap share a
+/// column, a cell overlapping several columns is a spanning header and does not widen them
+fn build_columns(lines: &[Vec<Cell>]) -> Vec<(f64, f64)> {
+    let mut grid = lines
+        .iter()
+        .filter(|line| line.len() > 1)
+        .collect::<Vec<_>>();
+    // the widest rows give the best first guess of the columns
+    grid.sort_by_key(|line| std::cmp::Reverse(line.len()));
+    let mut columns: Vec<(f64, f64)> = vec![];
+    for cell in grid.into_iter().flatten() {
+        let covered = columns
+            .iter()
+            .enumerate()
+            .filter(|(_, (start, end))| cell.overlaps(*start, *end))
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        match covered.as_slice() {
+            [] => columns.push((cell.x, cell.end_x)),
+            [i] => {
+                let column = &mut columns[*i];
+                column.0 = column.0.min(cell.x);
+                column.1 = column.1.max(cell.end_x);
+            }
+            _ => {}
+        }
+    }
+    columns.sort_by(|a, b| a.0.total_cmp(&b.0));
+    columns
+}
+
+/// set the columns covered by a cell, a cell between columns belongs to the nearest one
+fn place_cell(cell: &mut Cell, columns: &[(f64, f64)]) {
+    let covered = columns
+        .iter()
+        .enumerate()
+        .filter(|(_, (start, end))| cell.overlaps(*start, *end))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    if let (Some(first), Some(last)) = (covered.first(), covered.last()) {
+        cell.column = *first;
+        cell.span = last - first + 1;
+        return;
+    }
+    let center = (cell.x + cell.end_x) / 2f64;
+    let distance = |(start, end): &(f64, f64)| (center - (start + end) / 2f64).abs();
+    cell.column = columns
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| distance(a).total_cmp(&distance(b)))
+        .map_or(0, |(i, _)| i);
+}
+
+/// the rules running across at least the given share of the extent
+fn table_rules(rules: &[Rule], x: f64, end_x: f64) -> Vec<&Rule> {
+    rules
+        .iter()
+        .filter(|rule| rule.end_x.min(end_x) - rule.x.max(x) >= RULE_SPAN_RATIO * (end_x - x))
+        .collect()
+}
+
+/// the header rows of a table lie between the rules drawn above and below its first row with
+/// more than one cell, without such rules they are that row together with the rows right above
+/// it which group columns under a spanning label, rows above the top rule and rows holding a
+/// page label, such as the sponsor line, are no header
+fn mark_header(rows: &mut [Vec<Cell>], columns: usize, rules: &[&Rule], page_labels: &[Regex]) {
+    let top = rules.iter().map(|rule| rule.y).reduce(f64::max);
+    let below_top = |row: &Vec<Cell>| match top {
+        Some(top) => row.first().is_some_and(|cell| cell.y < top),
+        None => true,
+    };
+    let labelled = |row: &Vec<Cell>| {
+        row.iter()
+            .any(|cell| page_labels.iter().any(|label| label.is_match(&cell.text)))
+    };
+    let first = match rows
+        .iter()
+        .position(|row| row.len() > 1 && below_top(row) && !labelled(row))
+    {
+        Some(first) => first,
+        None => return,
+    };
+    let y = rows[first][0].y;
+    let top = rules
+        .iter()
+        .filter(|rule| rule.y > y)
+        .map(|rule| rule.y)
+        .reduce(f64::min);
+    let bottom = rules
+        .iter()
+        .filter(|rule| rule.y < y)
+        .map(|rule| rule.y)
+        .reduce(f64::max);
+    if let (Some(top), Some(bottom)) = (top, bottom) {
+        for row in rows.iter_mut() {
+            let header = row
+                .first()
+                .is_some_and(|cell| cell.y < top && cell.y > bottom);
+            row.iter_mut().for_each(|cell| cell.header = header);
+        }
+        return;
+    }
+    let mut start = first;
+    while start > 0
+        && !labelled(&rows[start - 1])
+        && rows[start - 1]
+            .iter()
+            .any(|cell| cell.span > 1 && cell.span < columns)
+    {
+        start -= 1;
+    }
+    for row in &mut rows[start..=first] {
+        row.iter_mut().for_each(|cell| cell.header = true);
+    }
+}
+
+/// rebuild the rows of a page as cells placed in columns, rows are ordered top down and hidden or
+/// rotated text is left out, rows holding one of the page labels are never taken for the header
+pub fn build_table(runs: &[TextRun], rules: &[Rule], page_labels: &[Regex]) -> Vec<Vec<Cell>> {
+    let upright = runs
+        .iter()
+        .filter(|run| run.visible && run.is_upright())
+        .cloned()
+        .collect::<Vec<_>>();
+    let mut rows = layout::build_lines(&upright)
+        .iter()
+        .map(|line| split_cells(line))
+        .filter(|row| !row.is_empty())
+        .collect::<Vec<_>>();
+    let columns = build_columns(&rows);
+    let (x, end_x) = match (columns.first(), columns.last()) {
+        (Some(first), Some(last)) => (first.0, last.1),
+        _ => return rows,
+    };
+    rows.iter_mut()
+        .flatten()
+        .for_each(|cell| place_cell(cell, &columns));
+    mark_header(
+        &mut rows,
+        columns.len(),
+        &table_rules(rules, x, end_x),
+        page_labels,
+    );
+    rows
+}
+
+/// left and right end of the header rows together with the baseline of the lowest one
+fn header_extent(rows: &[Vec<Cell>]) -> Option<(f64, f64, f64)> {
+    let header = rows.iter().flatten().filter(|cell| cell.header);
+    let x = header.clone().map(|cell| cell.x).reduce(f64::min)?;
+    let end_x = header.clone().map(|cell| cell.end_x).reduce(f64::max)?;
+    let header_y = header.map(|cell| cell.y).reduce(f64::min)?;
+    Some((x, end_x, header_y))
+}
+
+/// the rule closing the table below its body, the rule right under the header has no body rows
+/// above it, a table without one continues on the next page
+pub fn closing_rule<'a>(rows: &[Vec<Cell>], rules: &'a [Rule]) -> Option<&'a Rule> {
+    let (x, end_x, header_y) = header_extent(rows)?;
+    let body = rows
+        .iter()
+        .filter_map(|row| row.first())
+        .filter(|cell| cell.y < header_y)
+        .collect::<Vec<_>>();
+    table_rules(rules, x, end_x)
+        .into_iter()
+        .filter(|rule| rule.y < header_y)
+        .find(|rule| body.iter().any(|cell| cell.y > rule.y))
+}
+
+#[cfg(test)]
+mod table_test {
+    use super::*;
+    use crate::pdf::reader::test_util::run;
+
+    #[test]
+    fn build_table_test() {
+        let runs = vec![
+            run(
+                "Listing 16.2.1 Subject Disposition (Safety Set)",
+                72f64,
+                760f64,
+            ),
+            run("Treatment", 220f64, 730f64),
+            run("Subject", 72f64, 715f64),
+            run("Arm A", 180f64, 715f64),
+            run("Arm B", 260f64, 715f64),
+            run("01001", 72f64, 700f64),
+            run("12", 185f64, 700f64),
+            run("(3.5)", 197f64, 700f64),
+            run("7", 270f64, 700f64),
+            run("01002", 72f64, 685f64),
+            run("140", 180f64, 685f64),
+        ];
+        let rows = build_table(&runs, &[], &[]);
+        let texts = rows
+            .iter()
+            .map(|row| row.iter().map(|c| c.text.as_str()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            texts,
+            vec![
+                vec!["Listing 16.2.1 Subject Disposition (Safety Set)"],
+                vec!["Treatment"],
+                vec!["Subject", "Arm A", "Arm B"],
+                vec!["01001", "12 (3.5)", "7"],
+                vec!["01002", "140"],
+            ]
+        );
+        let columns = rows
+            .iter()
+            .map(|row| row.iter().map(|c| (c.column, c.span)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(columns[1], vec![(1, 2)]);
+        assert_eq!(columns[3], vec![(0, 1), (1, 1), (2, 1)]);
+        assert_eq!(columns[4], vec![(0, 1), (1, 1)]);
+        let header = rows
+            .iter()
+            .map(|row| row.iter().all(|c| c.header))
+            .collect::<Vec<_>>();
+        assert_eq!(header, vec![false, true, true, false, false]);
+    }
+
+    #[test]
+    fn header_rules_test() {
+        let rule = |y: f64, x: f64, end_x: f64| Rule { x, end_x, y };
+        // a header wrapped over two lines, the underline of the spanning label is no table rule
+        let runs = vec![
+            run("Table 14.1.1 Demographics", 72f64, 760f64),
+            run("Treatment", 220f64, 735f64),
+            run("Subject", 72f64, 720f64),
+            run("Arm A", 180f64, 720f64),
+            run("Arm B", 260f64, 720f64),
+            run("ID", 72f64, 708f64),
+            run("01001", 72f64, 690f64),
+            run("12", 185f64, 690f64),
+            run("7", 270f64, 690f64),
+            run("Note: footnote", 72f64, 650f64),
+        ];
+        let open = vec![
+            rule(750f64, 72f64, 300f64),
+            rule(730f64, 180f64, 290f64),
+            rule(700f64, 72f64, 300f64),
+        ];
+        let rows = build_table(&runs, &open, &[]);
+        let header = rows
+            .iter()
+            .map(|row| row.iter().all(|c| c.header))
+            .collect::<Vec<_>>();
+        assert_eq!(header, vec![false, true, true, true, false, false]);
+        assert_eq!(closing_rule(&rows, &open), None);
+
+        let mut closed = open.clone();
+        closed.push(rule(680f64, 72f64, 300f64));
+        let rows = build_table(&runs, &closed, &[]);
+        assert_eq!(closing_rule(&rows, &closed), closed.last());
+        // without header rows there is no table to close
+        assert_eq!(closing_rule(&rows[..1], &closed), None);
+
+        // a sponsor line with the page label above the top rule
+        let mut runs = runs;
+        runs.push(run("Page 1 of 2", 250f64, 775f64));
+        runs.push(run("AKESO Inc.", 72f64, 775f64));
+        let rows = build_table(&runs, &closed, &[]);
+        let header = rows
+            .iter()
+            .map(|row| row.iter().all(|c| c.header))
+            .collect::<Vec<_>>();
+        assert_eq!(header, vec![false, false, true, true, true, false, false]);
+    }
+
+    #[test]
+    fn page_label_test() {
+        // a sponsor line with the page label and no rules to tell it from the header
+        let runs = vec![
+            run("AKESO Inc.", 72f64, 760f64),
+            run("Page 1 of 3", 200f64, 760f64),
+            run("Subject", 72f64, 720f64),
+            run("Age", 200f64, 720f64),
+            run("01001", 72f64, 700f64),
+            run("45", 200f64, 700f64),
+        ];
+        let labels = [Regex::new(r"(?i)\bpage\s+(\d+)\s+of\s+(\d+)").unwrap()];
+        let header = |labels: &[Regex]| {
+            build_table(&runs, &[], labels)
+                .iter()
+                .map(|row| row.iter().all(|c| c.header))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(header(&labels), vec![false, true, false]);
+        assert_eq!(header(&[]), vec![true, false, false]);
+
+        // rules above the page but none below its sponsor line leave the table without header
+        let rules = [Rule {
+            x: 72f64,
+            end_x: 300f64,
+            y: 780f64,
+        }];
+        let rows = build_table(&runs[..2], &rules, &labels);
+        assert!(rows.iter().flatten().all(|c| !c.header));
+    }
+}
@@ -0,0 +1,57 @@
+use lopdf::{dictionary, Document, Object, Stream};
+
+use super::layout::TextRun;
+
+/// a text run of 10 point glyphs 5 points wide
+pub fn run(text: &str, x: f64, y: f64) -> TextRun {
+    TextRun {
+        text: text.into(),
+        x,
+        y,
+        end_x: x + text.len() as f64 * 5f64,
+        end_y: y,
+        size: 10f64,
+        space_gap: 1f64,
+        angle: 0f64,
+        visible: true,
+    }
+}
+
+/// content stream showing each text at its position
+pub fn show(texts: &[(f64, f64, &str)]) -> String {
+    texts
+        .iter()
+        .map(|(x, y, text)| format!("BT /F1 10 Tf 1 0 0 1 {} {} Tm ({}) Tj ET\n", x, y, text))
+        .collect()
+}
+
+/// a document with a page for each content stream
+pub fn document(contents: &[String]) -> Document {
+    let mut doc = Document::with_version("1.7");
+    let pages_id = doc.new_object_id();
+    let mut kids: Vec<Object> = vec![];
+    for content in contents {
+        let content_id = doc.add_object(Stream::new(dictionary! {}, content.clone().into_bytes()));
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Contents" => content_id,
+        });
+        kids.push(page_id.into());
+    }
+    let count = kids.len() as i64;
+    doc.objects.insert(
+        pages_id,
+        Object::Dictionary(dictionary! { "Type" => "Pages", "Kids" => kids, "Count" => count }),
+    );
+    let catalog = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+    doc.trailer.set("Root", catalog);
+    doc
+}
+
+/// the bytes of a pdf with a page for each content stream
+pub fn pdf(contents: &[String]) -> Vec<u8> {
+    let mut data = vec![];
+    document(contents).save_to(&mut data).unwrap();
+    data
+}
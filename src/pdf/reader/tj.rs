@@ -1,110 +1,177 @@
+use super::operation::Operand;
+
+/// An element of the strings shown by Tj, TJ, ' and "
 #[derive(Debug, PartialEq)]
-pub enum Text {
-    ASCII(String),
-    UNICODE(String),
+pub enum TjItem {
+    /// bytes of a literal string with escapes resolved, to be decoded with the font encoding
+    Literal(Vec<u8>),
+    /// bytes of a hex string
+    Hex(Vec<u8>),
+    /// position adjustment of a TJ array in thousandths of text space, negative values move right
+    Offset(f64),
 }
 
-const OPEN_BRACKET: u8 = b'[';
-const CLOSE_BRACKET: u8 = b']';
 const OPEN_PARENTHESIS: u8 = b'(';
 const CLOSE_PARENTHESIS: u8 = b')';
-const GREATER: u8 = b'>';
-const LESS: u8 = b'<';
-
-pub fn handle_tj(source: &[u8]) -> Text {
-    let mut s = String::new();
-    let mut content_start = 0;
-    let mut content_end = source.len() - 1;
+const BACKSLASH: u8 = b'\\';
+const NEWLINE: u8 = b'\n';
+const RETURN: u8 = b'\r';
+const UTF16_BOM: &[u8] = &[0xFE, 0xFF];
 
-    // if pdf convert by word, text content will be wrap in to bracket like [( )], so we need to extract the text content first
-    while content_start.lt(&source.len()) && content_end.ge(&0) && content_end.gt(&content_start) {
-        let head = source.get(content_start).unwrap();
-        let tail = source.get(content_end).unwrap();
-        if OPEN_BRACKET.eq(head) && CLOSE_BRACKET.eq(tail) {
-            content_start += 1;
-            break;
-        }
-        if OPEN_BRACKET.ne(head) {
-            content_start += 1;
-        }
-        if CLOSE_BRACKET.ne(tail) {
-            content_end -= 1;
+/// collect the strings and adjustments shown by a text operator, covers the `[(..)-6(..)] TJ`
+/// arrays written by office word as well as the `<..>Tj` written by wps
+pub fn handle_tj(operands: &[Operand]) -> Vec<TjItem> {
+    let mut items = vec![];
+    for operand in operands {
+        match operand {
+            Operand::Literal(bytes) => items.push(TjItem::Literal(bytes.clone())),
+            Operand::Hex(bytes) => items.push(TjItem::Hex(bytes.clone())),
+            Operand::Number(n) => items.push(TjItem::Offset(*n)),
+            Operand::Array(array) => items.extend(handle_tj(array)),
+            _ => {}
         }
     }
+    items
+}
 
-    // content_start < content_end means perhaps pdf convert by word, else means convert by wps
-    let source = if content_start < content_end {
-        source.get(content_start..content_end).unwrap()
-    } else {
-        source
-    };
-
-    let mut content_indexes = vec![];
-    let mut content_index = (0, 0);
-    // a mark if content is present in unicode pattern
-    let mut unicode_content = false;
-    let mut content_parsing = false;
-    source.iter().enumerate().for_each(|(i, c)| {
-        if OPEN_PARENTHESIS.eq(c) && !content_parsing {
-            unicode_content = false;
-            content_index.0 = i + 1;
-            content_parsing = true;
+/// decode the literal string starting with the parenthesis at `start`, returns the bytes and the
+/// position right after the closing parenthesis
+pub fn literal_string(source: &[u8], start: usize) -> (Vec<u8>, usize) {
+    let mut bytes = vec![];
+    let mut depth = 0;
+    let mut i = start;
+    while let Some(c) = source.get(i) {
+        i += 1;
+        match *c {
+            OPEN_PARENTHESIS => {
+                depth += 1;
+                if depth == 1 {
+                    continue;
+                }
+            }
+            CLOSE_PARENTHESIS => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            BACKSLASH => {
+                let escaped = match source.get(i) {
+                    Some(c) => *c,
+                    None => break,
+                };
+                i += 1;
+                match escaped {
+                    b'n' => bytes.push(NEWLINE),
+                    b'r' => bytes.push(RETURN),
+                    b't' => bytes.push(b'\t'),
+                    b'b' => bytes.push(0x08),
+                    b'f' => bytes.push(0x0C),
+                    b'0'..=b'7' => {
+                        let mut code = (escaped - b'0') as u32;
+                        let mut digits = 1;
+                        while let Some(d @ b'0'..=b'7') = source.get(i) {
+                            if digits == 3 {
+                                break;
+                            }
+                            code = code * 8 + (d - b'0') as u32;
+                            digits += 1;
+                            i += 1;
+                        }
+                        bytes.push(code as u8);
+                    }
+                    // a backslash at the end of a line continues the string on the next line
+                    RETURN => {
+                        if source.get(i) == Some(&NEWLINE) {
+                            i += 1;
+                        }
+                    }
+                    NEWLINE => {}
+                    // \( \) \\ and unknown escapes keep the escaped character only
+                    other => bytes.push(other),
+                }
+                continue;
+            }
+            RETURN => {
+                // an unescaped end of line is read as a single newline
+                if source.get(i) == Some(&NEWLINE) {
+                    i += 1;
+                }
+                bytes.push(NEWLINE);
+                continue;
+            }
+            _ => {}
         }
-        if CLOSE_PARENTHESIS.eq(c) && !unicode_content {
-            content_index.1 = i;
-            content_indexes.push(content_index);
-            content_parsing = false;
-        }
-        if LESS.eq(c) && !content_parsing {
-            unicode_content = true;
-            content_index.0 = i + 1;
-            content_parsing = true;
-        }
-        if GREATER.eq(c) && unicode_content {
-            content_index.1 = i;
-            content_indexes.push(content_index);
-            content_parsing = false;
-        }
-    });
-    content_indexes.iter().for_each(|(start, end)| {
-        if let Some(data) = source.get(*start..*end) {
-            s.push_str(String::from_utf8_lossy(data).to_string().as_str());
-        }
-    });
-    if unicode_content {
-        Text::UNICODE(s)
-    } else {
-        Text::ASCII(s)
+        bytes.push(*c);
     }
+    (bytes, i)
+}
+
+/// text of a string starting with the UTF-16BE byte order mark, such strings bypass the font
+pub fn utf16_text(source: &[u8]) -> Option<String> {
+    let body = source.strip_prefix(UTF16_BOM)?;
+    let units = body
+        .chunks(2)
+        .map(|pair| u16::from_be_bytes([pair[0], *pair.get(1).unwrap_or(&0)]))
+        .collect::<Vec<_>>();
+    Some(String::from_utf16_lossy(&units))
 }
 
 #[cfg(test)]
 mod tj_test {
+    use super::super::operation::parse_operations;
     use super::*;
+    fn tj(source: &str) -> Vec<TjItem> {
+        handle_tj(&parse_operations(source.as_bytes())[0].operands)
+    }
+
     #[test]
     fn handle_tj_test() {
-        let content = "[(p)-6(r)5(o)7(g)7(r)5(a)-3(m)]".as_bytes();
-        let content = handle_tj(content);
-        assert_eq!(content, Text::ASCII("program".into()));
-        let content = "[( )]".as_bytes();
-        let content = handle_tj(content);
-        assert_eq!(content, Text::ASCII(" ".into()));
-        let content = "[<22EB2BC71C151D4F02C42E4430A6>]".as_bytes();
-        let content = handle_tj(content);
+        let content = tj("[(p)-6(r)5(o)7(g)7(r)5(a)-3(m)]TJ");
+        assert_eq!(content.len(), 13);
+        assert_eq!(content[0], TjItem::Literal("p".into()));
+        assert_eq!(content[1], TjItem::Offset(-6f64));
+        assert_eq!(content[12], TjItem::Literal("m".into()));
+        let content = tj("[( )]TJ");
+        assert_eq!(content, vec![TjItem::Literal(" ".into())]);
+        let content = tj("[<22EB2BC71C151D4F02C42E4430A6>]TJ");
         assert_eq!(
             content,
-            Text::UNICODE("22EB2BC71C151D4F02C42E4430A6".into())
+            vec![TjItem::Hex(vec![
+                0x22, 0xEB, 0x2B, 0xC7, 0x1C, 0x15, 0x1D, 0x4F, 0x02, 0xC4, 0x2E, 0x44, 0x30, 0xA6
+            ])]
         );
-        let content = "[<1BE91E783546>11<0A2702D6>]".as_bytes();
-        let content = handle_tj(content);
-        assert_eq!(content, Text::UNICODE("1BE91E7835460A2702D6".into()));
-        let content = r"[(\()] ".as_bytes();
-        let content = handle_tj(content);
-        assert_eq!(content, Text::ASCII(r"\(".into()));
+        let content = tj("[<1BE91E783546>11<0A2702D6>]TJ");
+        assert_eq!(content[1], TjItem::Offset(11f64));
+        assert_eq!(content[2], TjItem::Hex(vec![0x0A, 0x27, 0x02, 0xD6]));
+        let content = tj(r"[(\()] TJ");
+        assert_eq!(content, vec![TjItem::Literal("(".into())]);
 
         // content in wps
-        let content = "<0026>Tj 139.188 -0 TD<0036>Tj 117.594 -0 TD<0035>".as_bytes();
-        let content = handle_tj(content);
-        assert_eq!(content, Text::UNICODE("002600360035".into()));
+        let content = tj("<0026>Tj");
+        assert_eq!(content, vec![TjItem::Hex(vec![0x00, 0x26])]);
+
+        let content = tj("[(Table 14.1 (Safety Set))]TJ");
+        assert_eq!(
+            content,
+            vec![TjItem::Literal("Table 14.1 (Safety Set)".into())]
+        );
+    }
+
+    #[test]
+    fn literal_string_test() {
+        let content = r"(a\)b\\c\050d\0533\7e\
+f)g"
+        .as_bytes();
+        let (bytes, end) = literal_string(content, 0);
+        assert_eq!(bytes, b"a)b\\c(d+3\x07ef".to_vec());
+        assert_eq!(content.get(end), Some(&b'g'));
+
+        let content = "(line\r\nbreak)".as_bytes();
+        assert_eq!(literal_string(content, 0).0, b"line\nbreak".to_vec());
+
+        let bytes = [0xFE, 0xFF, 0x00, 0x41, 0x4E, 0x2D];
+        assert_eq!(utf16_text(&bytes), Some("A中".into()));
+        assert_eq!(utf16_text(b"AB"), None);
     }
 }
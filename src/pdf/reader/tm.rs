@@ -1,38 +1,66 @@
-const SPACE: u8 = b' ';
-/// handle the position information define in tm row
+use super::operation::Operand;
+
+/// affine matrix `[a b c d e f]` as used by Tm and cm
+pub type Matrix = [f64; 6];
+
+pub const IDENTITY: Matrix = [1f64, 0f64, 0f64, 1f64, 0f64, 0f64];
+
+/// handle the position information define in tm operands
 ///
 /// ```rust
 ///     fn handle_tm_test() {
-///         let content = "1 0 0 1 435.29 473.14 ".as_bytes();
-///         let pos = handle_tm(content);
+///         let operation = parse_operations("1 0 0 1 435.29 473.14 Tm".as_bytes());
+///         let pos = handle_tm(&operation[0].operands);
 ///         assert_eq!(pos, [1f64, 0f64, 0f64, 1f64, 435.29f64, 473.14f64]);
 ///     }
 /// ```
-pub fn handle_tm(source: &[u8]) -> [f64; 6] {
-    let mut position: [f64; 6] = [0f64; 6];
-    let mut pos = 0;
-    let mut start = 0;
-    for (i, c) in source.iter().enumerate() {
-        if SPACE.eq(c) {
-            if let Some(n) = source.get(start..i) {
-                if let Ok(n) = String::from_utf8_lossy(n).to_string().parse::<f64>() {
-                    position[pos] = n;
-                }
-                pos += 1;
-            }
-            start = i + 1;
+pub fn handle_tm(operands: &[Operand]) -> Matrix {
+    let mut position: Matrix = [0f64; 6];
+    for (pos, operand) in operands.iter().take(6).enumerate() {
+        if let Some(n) = operand.as_number() {
+            position[pos] = n;
         }
     }
     position
 }
 
+/// the product `m1 × m2`, which applies m1 first and then m2
+pub fn multiply(m1: &Matrix, m2: &Matrix) -> Matrix {
+    [
+        m1[0] * m2[0] + m1[1] * m2[2],
+        m1[0] * m2[1] + m1[1] * m2[3],
+        m1[2] * m2[0] + m1[3] * m2[2],
+        m1[2] * m2[1] + m1[3] * m2[3],
+        m1[4] * m2[0] + m1[5] * m2[2] + m2[4],
+        m1[4] * m2[1] + m1[5] * m2[3] + m2[5],
+    ]
+}
+
+pub fn translate(tx: f64, ty: f64) -> Matrix {
+    [1f64, 0f64, 0f64, 1f64, tx, ty]
+}
+
+/// length of a unit vector along the x axis after the transformation
+pub fn scale(m: &Matrix) -> f64 {
+    m[0].hypot(m[1])
+}
+
 #[cfg(test)]
 mod tm_test {
+    use super::super::operation::parse_operations;
     use super::*;
     #[test]
     fn handle_tm_test() {
-        let content = "1 0 0 1 435.29 473.14 ".as_bytes();
-        let pos = handle_tm(content);
+        let operation = parse_operations("1 0 0 1 435.29 473.14 Tm".as_bytes());
+        let pos = handle_tm(&operation[0].operands);
         assert_eq!(pos, [1f64, 0f64, 0f64, 1f64, 435.29f64, 473.14f64]);
     }
+
+    #[test]
+    fn multiply_test() {
+        let tm = [2f64, 0f64, 0f64, 2f64, 10f64, 20f64];
+        assert_eq!(multiply(&translate(5f64, 0f64), &tm)[4], 20f64);
+        assert_eq!(multiply(&IDENTITY, &tm), tm);
+        assert_eq!(scale(&tm), 2f64);
+    }
 }
@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+
+use super::glyph::glyph_to_unicode;
+
+const CMAP: &[u8] = b"cmap";
+const POST: &[u8] = b"post";
+const TTC: &[u8] = b"ttcf";
+// glyph names of the standard macintosh ordering used by post table format 1.0 and 2.0
+const MAC_GLYPH_NAMES: &str = ".notdef .null nonmarkingreturn space exclam quotedbl numbersign \
+dollar percent ampersand quotesingle parenleft parenright asterisk plus comma hyphen period \
+slash zero one two three four five six seven eight nine colon semicolon less equal greater \
+question at A B C D E F G H I J K L M N O P Q R S T U V W X Y Z bracketleft backslash \
+bracketright asciicircum underscore grave a b c d e f g h i j k l m n o p q r s t u v w x y z \
+braceleft bar braceright asciitilde Adieresis Aring Ccedilla Eacute Ntilde Odieresis Udieresis \
+aacute agrave acircumflex adieresis atilde aring ccedilla eacute egrave ecircumflex edieresis \
+iacute igrave icircumflex idieresis ntilde oacute ograve ocircumflex odieresis otilde uacute \
+ugrave ucircumflex udieresis dagger degree cent sterling section bullet paragraph germandbls \
+registered copyright trademark acute dieresis notequal AE Oslash infinity plusminus lessequal \
+greaterequal yen mu partialdiff summation product pi integral ordfeminine ordmasculine Omega ae \
+oslash questiondown exclamdown logicalnot radical florin approxequal Delta guillemotleft \
+guillemotright ellipsis nonbreakingspace Agrave Atilde Otilde OE oe endash emdash quotedblleft \
+quotedblright quoteleft quoteright divide lozenge ydieresis Ydieresis fraction currency \
+guilsinglleft guilsinglright fi fl daggerdbl periodcentered quotesinglbase quotedblbase \
+perthousand Acircumflex Ecircumflex Aacute Edieresis Egrave Iacute Icircumflex Idieresis Igrave \
+Oacute Ocircumflex apple Ograve Uacute Ucircumflex Ugrave dotlessi circumflex tilde macron breve \
+dotaccent ring cedilla hungarumlaut ogonek caron Lslash lslash Scaron scaron Zcaron zcaron \
+brokenbar Eth eth Yacute yacute Thorn thorn minus multiply onesuperior twosuperior threesuperior \
+onehalf onequarter threequarters franc Gbreve gbreve Idotaccent Scedilla scedilla Cacute cacute \
+Ccaron ccaron dcroat";
+
+/// build a glyph id to unicode map from the cmap and post tables of an embedded TrueType or
+/// OpenType font program, glyphs reachable from the cmap take precedence over post names
+pub fn glyph_unicode_map(font: &[u8]) -> Option<HashMap<u16, String>> {
+    let tables = table_directory(font)?;
+    let mut map = HashMap::new();
+    if let Some(cmap) = tables.get(CMAP) {
+        for (code, gid) in parse_cmap(cmap).unwrap_or_default() {
+            if gid == 0 || (0xE000..=0xF8FF).contains(&code) {
+                continue;
+            }
+            if let Some(c) = char::from_u32(code) {
+                let text = map.entry(gid).or_insert(String::new());
+                // keep the lowest code point when a glyph is mapped more than once
+                if text.is_empty() || text.chars().next().is_some_and(|first| c < first) {
+                    *text = c.to_string();
+                }
+            }
+        }
+    }
+    if let Some(post) = tables.get(POST) {
+        for (gid, name) in parse_post(post).unwrap_or_default() {
+            if map.contains_key(&gid) {
+                continue;
+            }
+            if let Some(text) = glyph_to_unicode(&name) {
+                map.insert(gid, text);
+            }
+        }
+    }
+    if map.is_empty() {
+        None
+    } else {
+        Some(map)
+    }
+}
+
+fn u16_at(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn table_directory(font: &[u8]) -> Option<HashMap<&[u8], &[u8]>> {
+    // a font collection is read from its first font
+    let start = if font.get(..4) == Some(TTC) {
+        u32_at(font, 12)? as usize
+    } else {
+        0
+    };
+    let num_tables = u16_at(font, start + 4)? as usize;
+    let mut tables = HashMap::new();
+    for i in 0..num_tables {
+        let record = start + 12 + i * 16;
+        let tag = font.get(record..record + 4)?;
+        let offset = u32_at(font, record + 8)? as usize;
+        let length = u32_at(font, record + 12)? as usize;
+        if let Some(table) = font.get(offset..offset.saturating_add(length)) {
+            tables.insert(tag, table);
+        }
+    }
+    Some(tables)
+}
+
+/// code point and glyph id pairs of the best unicode subtable
+fn parse_cmap(cmap: &[u8]) -> Option<Vec<(u32, u16)>> {
+    let num_tables = u16_at(cmap, 2)? as usize;
+    let mut best: Option<(u8, usize)> = None;
+    for i in 0..num_tables {
+        let record = 4 + i * 8;
+        let platform = u16_at(cmap, record)?;
+        let encoding = u16_at(cmap, record + 2)?;
+        let offset = u32_at(cmap, record + 4)? as usize;
+        let rank = match (platform, encoding) {
+            (3, 10) | (0, 4) | (0, 6) => 3,
+            (3, 1) | (0, _) => 2,
+            _ => continue,
+        };
+        let better = match best {
+            Some((best_rank, _)) => rank > best_rank,
+            None => true,
+        };
+        if better {
+            best = Some((rank, offset));
+        }
+    }
+    let subtable = cmap.get(best?.1..)?;
+    match u16_at(subtable, 0)? {
+        0 => Some(
+            (0..256)
+                .filter_map(|code| Some((code as u32, *subtable.get(6 + code)? as u16)))
+                .collect(),
+        ),
+        4 => parse_cmap_format4(subtable),
+        6 => {
+            let first = u16_at(subtable, 6)? as u32;
+            let count = u16_at(subtable, 8)? as usize;
+            Some(
+                (0..count)
+                    .filter_map(|i| Some((first + i as u32, u16_at(subtable, 10 + i * 2)?)))
+                    .collect(),
+            )
+        }
+        12 => {
+            let groups = u32_at(subtable, 12)? as usize;
+            let mut pairs = vec![];
+            for i in 0..groups {
+                let group = 16 + i * 12;
+                let start = u32_at(subtable, group)?;
+                let end = u32_at(subtable, group + 4)?;
+                let glyph = u32_at(subtable, group + 8)?;
+                if end < start || end - start > 0xFFFF {
+                    continue;
+                }
+                for code in start..=end {
+                    pairs.push((code, (glyph + code - start) as u16));
+                }
+            }
+            Some(pairs)
+        }
+        _ => None,
+    }
+}
+
+fn parse_cmap_format4(subtable: &[u8]) -> Option<Vec<(u32, u16)>> {
+    let seg_count = u16_at(subtable, 6)? as usize / 2;
+    let end_codes = 14;
+    let start_codes = end_codes + seg_count * 2 + 2;
+    let id_deltas = start_codes + seg_count * 2;
+    let id_range_offsets = id_deltas + seg_count * 2;
+    let mut pairs = vec![];
+    for i in 0..seg_count {
+        let end = u16_at(subtable, end_codes + i * 2)?;
+        let start = u16_at(subtable, start_codes + i * 2)?;
+        let delta = u16_at(subtable, id_deltas + i * 2)?;
+        let range_offset = u16_at(subtable, id_range_offsets + i * 2)? as usize;
+        if start == 0xFFFF || end < start {
+            continue;
+        }
+        for code in start..=end {
+            let gid = if range_offset == 0 {
+                code.wrapping_add(delta)
+            } else {
+                let index = id_range_offsets + i * 2 + range_offset + (code - start) as usize * 2;
+                match u16_at(subtable, index) {
+                    Some(0) | None => 0,
+                    Some(gid) => gid.wrapping_add(delta),
+                }
+            };
+            pairs.push((code as u32, gid));
+        }
+    }
+    Some(pairs)
+}
+
+/// glyph names of a post table in format 1.0 or 2.0
+fn parse_post(post: &[u8]) -> Option<Vec<(u16, String)>> {
+    let mac_names = MAC_GLYPH_NAMES.split_whitespace().collect::<Vec<_>>();
+    match u32_at(post, 0)? {
+        0x00010000 => Some(
+            mac_names
+                .iter()
+                .enumerate()
+                .map(|(gid, name)| (gid as u16, name.to_string()))
+                .collect(),
+        ),
+        0x00020000 => {
+            let num_glyphs = u16_at(post, 32)? as usize;
+            let mut custom_names = vec![];
+            let mut offset = 34 + num_glyphs * 2;
+            while let Some(len) = post.get(offset) {
+                let name = post.get(offset + 1..offset + 1 + *len as usize)?;
+                custom_names.push(String::from_utf8_lossy(name).to_string());
+                offset += 1 + *len as usize;
+            }
+            let mut names = vec![];
+            for gid in 0..num_glyphs {
+                let index = u16_at(post, 34 + gid * 2)? as usize;
+                let name = if index < mac_names.len() {
+                    mac_names.get(index).map(|n| n.to_string())
+                } else {
+                    custom_names.get(index - mac_names.len()).cloned()
+                };
+                if let Some(name) = name {
+                    names.push((gid as u16, name));
+                }
+            }
+            Some(names)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod truetype_test {
+    use super::*;
+
+    fn font(tables: &[(&[u8], Vec<u8>)]) -> Vec<u8> {
+        let mut data = vec![0, 1, 0, 0];
+        data.extend_from_slice(&(tables.len() as u16).to_be_bytes());
+        data.extend_from_slice(&[0; 6]);
+        let mut offset = 12 + tables.len() * 16;
+        for (tag, table) in tables {
+            data.extend_from_slice(tag);
+            data.extend_from_slice(&[0; 4]);
+            data.extend_from_slice(&(offset as u32).to_be_bytes());
+            data.extend_from_slice(&(table.len() as u32).to_be_bytes());
+            offset += table.len();
+        }
+        for (_, table) in tables {
+            data.extend_from_slice(table);
+        }
+        data
+    }
+
+    fn words(values: &[u16]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_be_bytes()).collect()
+    }
+
+    #[test]
+    fn glyph_unicode_map_test() {
+        // format 4 subtable: 'A'..'C' -> gid 1..3 through delta, 0x4E2D -> gid 4 through the array
+        let mut subtable = words(&[4, 0, 0, 6, 0, 0, 0]);
+        subtable.extend(words(&[0x0043, 0x4E2D, 0xFFFF, 0]));
+        subtable.extend(words(&[0x0041, 0x4E2D, 0xFFFF]));
+        subtable.extend(words(&[(1u16).wrapping_sub(0x41), 0, 1]));
+        subtable.extend(words(&[0, 4, 0, 4]));
+        let mut cmap = words(&[0, 1, 3, 1, 0, 12]);
+        cmap.extend(subtable);
+
+        // post format 2.0: gid 5 is a custom ligature name, gid 6 a standard mac name
+        let mut post = vec![0, 2, 0, 0];
+        post.extend_from_slice(&[0; 28]);
+        post.extend(words(&[7, 0, 36, 37, 38, 0, 258, 3]));
+        post.push(3);
+        post.extend_from_slice(b"f_i");
+
+        let data = font(&[(CMAP, cmap), (POST, post)]);
+        let map = glyph_unicode_map(&data).unwrap();
+        assert_eq!(map.get(&1).map(|s| s.as_str()), Some("A"));
+        assert_eq!(map.get(&3).map(|s| s.as_str()), Some("C"));
+        assert_eq!(map.get(&4).map(|s| s.as_str()), Some("中"));
+        assert_eq!(map.get(&5).map(|s| s.as_str()), Some("fi"));
+        assert_eq!(map.get(&6).map(|s| s.as_str()), Some(" "));
+        assert_eq!(map.get(&0), None);
+    }
+}
@@ -1,40 +1,252 @@
-use std::path::Path;
+use std::{
+    io::{Read, Seek},
+    path::Path,
+};
 
 use anyhow::Ok;
+use regex::Regex;
 
-use crate::{pdf::reader::PDFReader, report::Report};
+use crate::{
+    pdf::reader::PDFReader,
+    report::{Kind, Report},
+};
 
-pub fn probe(pdf_path: &Path) -> anyhow::Result<Report> {
-    let reader = PDFReader::new(&pdf_path)?;
-    let r = Report::new(pdf_path.to_string_lossy().to_string().as_str());
-    for (page_number, page) in reader.content().iter().enumerate() {
-        if page.is_empty() {
-            r.append_void(page_number + 1);
-            continue;
+mod blocks;
+mod body;
+mod fill;
+mod footnote;
+mod group;
+mod header;
+mod numbering;
+mod overflow;
+mod sort;
+mod split;
+#[cfg(test)]
+mod test_util;
+mod title;
+
+// page labels such as "Page 3 of 12" or "第 3 页 共 12 页", page number first and page count second
+const PAGE_LABELS: [&str; 2] = [
+    r"(?i)\bpage\s+(\d+)\s+of\s+(\d+)",
+    r"第\s*(\d+)\s*页\s*[,，/]?\s*共\s*(\d+)\s*页",
+];
+
+// rows starting like a footnote, a note, an abbreviation list, the program path or a numbered note
+const FOOTNOTES: [&str; 3] = [
+    r"(?i)^(notes?|abbreviations?|source|program|footnotes?|output)\s*[:：]",
+    r"^(\[\d+\]|\(\d+\)|\d+\.|[a-z]\))\s",
+    r"^(注|缩写|来源|程序)",
+];
+
+// markers of a group continued from the previous page
+const CONTINUED: [&str; 2] = [r"(?i)\(cont(inued|\.)?\)", r"[(（]续[)）]"];
+
+// pages holding less than this share of the typical body rows are underfull
+const UNDERFULL_RATIO: f64 = 0.3;
+// pages after a break holding this many body rows or fewer hold orphan rows
+const ORPHAN_ROWS: usize = 2;
+
+pub use footnote::FootnotePlacement;
+
+/// The settings of the checks run over the pages of an output
+pub struct Probe {
+    page_labels: Vec<Regex>,
+    footnotes: Vec<Regex>,
+    footnote_placement: FootnotePlacement,
+    fill: fill::Fill,
+    key_columns: Vec<usize>,
+    continued: Vec<Regex>,
+    sort_columns: Vec<usize>,
+    margins: [f64; 4],
+}
+
+impl Default for Probe {
+    fn default() -> Self {
+        Probe {
+            page_labels: build_patterns(&PAGE_LABELS).unwrap(),
+            footnotes: build_patterns(&FOOTNOTES).unwrap(),
+            footnote_placement: FootnotePlacement::EveryPage,
+            fill: fill::Fill {
+                ratio: UNDERFULL_RATIO,
+                orphan_rows: ORPHAN_ROWS,
+            },
+            key_columns: vec![],
+            continued: build_patterns(&CONTINUED).unwrap(),
+            sort_columns: vec![],
+            margins: [0f64; 4],
         }
-        for (row_number, row) in page.iter().enumerate() {
-            if row.is_empty() {
-                continue;
+    }
+}
+
+fn build_patterns(patterns: &[&str]) -> Result<Vec<Regex>, regex::Error> {
+    patterns.iter().map(|pattern| Regex::new(pattern)).collect()
+}
+
+impl Probe {
+    /// patterns of the page label, each capturing the page number first and the page count second
+    pub fn set_page_labels(mut self, patterns: &[&str]) -> anyhow::Result<Self> {
+        self.page_labels = build_patterns(patterns)?;
+        Ok(self)
+    }
+
+    /// patterns of the rows which are footnotes when found outside the table, such as on a page
+    /// holding nothing else
+    pub fn set_footnotes(mut self, patterns: &[&str]) -> anyhow::Result<Self> {
+        self.footnotes = build_patterns(patterns)?;
+        Ok(self)
+    }
+
+    /// where the study convention puts the footnotes of an output
+    pub fn set_footnote_placement(mut self, placement: FootnotePlacement) -> Self {
+        self.footnote_placement = placement;
+        self
+    }
+
+    /// pages holding less than this share of the typical body rows of the output are underfull
+    pub fn set_underfull_ratio(mut self, ratio: f64) -> Self {
+        self.fill.ratio = ratio;
+        self
+    }
+
+    /// pages after a break holding this many body rows or fewer hold orphan rows
+    pub fn set_orphan_rows(mut self, orphan_rows: usize) -> Self {
+        self.fill.orphan_rows = orphan_rows;
+        self
+    }
+
+    /// zero-based columns holding the group key of a listing, such as treatment and subject, the
+    /// group checks are skipped without them
+    pub fn set_key_columns(mut self, key_columns: &[usize]) -> Self {
+        self.key_columns = key_columns.to_vec();
+        self
+    }
+
+    /// patterns of the marker of a group continued from the previous page
+    pub fn set_continued(mut self, patterns: &[&str]) -> anyhow::Result<Self> {
+        self.continued = build_patterns(patterns)?;
+        Ok(self)
+    }
+
+    /// zero-based columns by which the rows of a listing are sorted, none to skip the sort check
+    pub fn set_sort_columns(mut self, sort_columns: &[usize]) -> Self {
+        self.sort_columns = sort_columns.to_vec();
+        self
+    }
+
+    /// margins kept free of text inside the page box, in points
+    pub fn set_margins(mut self, left: f64, bottom: f64, right: f64, top: f64) -> Self {
+        self.margins = [left, bottom, right, top];
+        self
+    }
+
+    pub fn probe(&self, pdf_path: &Path) -> anyhow::Result<Report> {
+        let reader = PDFReader::new(pdf_path)?;
+        self.probe_reader(&reader, pdf_path.to_string_lossy().to_string().as_str())
+    }
+
+    /// probe a pdf held in memory, file is the name given in the report
+    pub fn probe_bytes(&self, file: &str, data: &[u8]) -> anyhow::Result<Report> {
+        self.probe_reader(&PDFReader::from_bytes(data)?, file)
+    }
+
+    /// probe a pdf read from any source, file is the name given in the report
+    pub fn probe_read<R: Read + Seek>(&self, file: &str, source: R) -> anyhow::Result<Report> {
+        self.probe_reader(&PDFReader::from_read(source)?, file)
+    }
+
+    fn probe_reader(&self, reader: &PDFReader, file: &str) -> anyhow::Result<Report> {
+        let r = Report::new(file);
+        let mut labels = Vec::with_capacity(reader.page_count());
+        let mut pages = Vec::with_capacity(reader.page_count());
+        let mut figures = Vec::with_capacity(reader.page_count());
+        for (page_number, page) in reader.pages().enumerate() {
+            let page_number = page_number + 1;
+            let page = page?;
+            let rows = page.rows(false);
+            labels.push(numbering::page_label(&rows, &self.page_labels));
+            if title_missing(&rows) {
+                r.append_void(page_number);
             }
-            let row = row.trim();
-            if row.is_empty() {
-                if row_number.eq(&(page.len() - 1)) {
-                    r.append_void(page_number + 1);
-                }
-                continue;
+            let table = page.table(&self.page_labels);
+            overflow::check_overflow(page_number, &page, &table, self.margins, &r);
+            let blocks = blocks::split_blocks(table, &page.rules(), &self.footnotes);
+            if body::body_empty(&blocks) {
+                r.append_finding(
+                    page_number,
+                    Kind::EmptyBody,
+                    "nothing below the title and column header",
+                );
             }
-            if !(row.contains("康方") || row.to_uppercase().starts_with("AKESO")) {
-                r.append_void(page_number + 1);
+            pages.push(blocks);
+            // images and drawn lines other than table rules make a figure
+            let drawn = page.segments.iter().any(|s| !s.is_horizontal());
+            figures.push(drawn || !page.images.is_empty());
+        }
+        numbering::check_labels(&labels, &r);
+        // page labels and continuation markers change from page to page
+        let masks = self
+            .page_labels
+            .iter()
+            .chain(&self.continued)
+            .cloned()
+            .collect::<Vec<_>>();
+        let titles = pages
+            .iter()
+            .map(|blocks| title::title_block(blocks, &masks))
+            .collect::<Vec<_>>();
+        title::check_titles(&titles, &r);
+        body::check_title_only(&pages, &titles, &figures, &masks, &r);
+        header::check_headers(&pages, &r);
+        footnote::check_footnotes(&pages, self.footnote_placement, &self.footnotes, &masks, &r);
+        fill::check_fill(&pages, self.fill, &r);
+        split::check_split_rows(&pages, &r);
+        group::check_groups(&pages, &self.key_columns, &self.continued, &r);
+        sort::check_sort_order(&pages, &self.sort_columns, &self.continued, &r);
+        Ok(r)
+    }
+}
+
+pub fn probe(pdf_path: &Path) -> anyhow::Result<Report> {
+    Probe::default().probe(pdf_path)
+}
+
+/// probe a pdf held in memory, file is the name given in the report
+pub fn probe_bytes(file: &str, data: &[u8]) -> anyhow::Result<Report> {
+    Probe::default().probe_bytes(file, data)
+}
+
+/// probe a pdf read from any source, file is the name given in the report
+pub fn probe_read<R: Read + Seek>(file: &str, source: R) -> anyhow::Result<Report> {
+    Probe::default().probe_read(file, source)
+}
+
+/// whether the first row of a page is not the sponsor line, which means a break happened
+fn title_missing(page: &[String]) -> bool {
+    if page.is_empty() {
+        return true;
+    }
+    for (row_number, row) in page.iter().enumerate() {
+        if row.is_empty() {
+            continue;
+        }
+        let row = row.trim();
+        if row.is_empty() {
+            if row_number.eq(&(page.len() - 1)) {
+                return true;
             }
-            break;
+            continue;
         }
+        return !(row.contains("康方") || row.to_uppercase().starts_with("AKESO"));
     }
-    Ok(r)
+    false
 }
 
 #[cfg(test)]
 mod test_probe {
     use super::*;
+    use crate::pdf::reader::test_util::{pdf, show};
+    use test_util::pages_with;
+
     #[test]
     fn probe_test() {
         let p = Path::new(
@@ -52,4 +264,315 @@ mod test_probe {
         let report = probe(p).unwrap();
         println!("{:?}", report);
     }
+
+    #[test]
+    fn probe_bytes_test() {
+        let title = (72f64, 760f64, "AKESO Inc.");
+        let header = [(72f64, 720f64, "Subject"), (200f64, 720f64, "Age")];
+        let row = [(72f64, 700f64, "01001"), (200f64, 700f64, "45")];
+        let data = pdf(&[
+            show(&[title, header[0], header[1], row[0], row[1]]),
+            show(&[title, header[0], header[1]]),
+            show(&[(72f64, 760f64, "Listing")]),
+            // a valid page of a listing without records has no column header
+            show(&[title, (72f64, 720f64, "No subjects met the criteria.")]),
+        ]);
+        let report = probe_bytes("memory.pdf", &data).unwrap();
+        assert_eq!(report.file(), "memory.pdf");
+        // an empty body is a finding, only a missing title marks the page void
+        assert_eq!(report.void(), vec![3]);
+        assert_eq!(pages_with(&report, Kind::EmptyBody), vec![2]);
+        let report = probe_read("reader.pdf", std::io::Cursor::new(data)).unwrap();
+        assert_eq!(report.void(), vec![3]);
+    }
+
+    #[test]
+    fn title_only_test() {
+        let sponsor = (72f64, 760f64, "AKESO Inc.");
+        let title = (72f64, 745f64, "Figure 14.2.1 Kaplan-Meier Plot");
+        let rules = "72 722 m 320 722 l S 72 704 m 320 704 l S";
+        let data = pdf(&[
+            show(&[
+                sponsor,
+                title,
+                (72f64, 710f64, "Subject"),
+                (200f64, 710f64, "Age"),
+                (72f64, 690f64, "01001"),
+                (200f64, 690f64, "45"),
+            ]) + rules,
+            // the plot was pushed onto the next page
+            show(&[sponsor, title]),
+            show(&[sponsor, title]) + "100 300 m 200 500 l 300 550 l S",
+            show(&[sponsor, title])
+                + "q 400 0 0 300 100 300 cm BI /W 1 /H 1 /CS /G /BPC 8 ID \x00 EI Q",
+        ]);
+        let report = probe_bytes("figure.pdf", &data).unwrap();
+        assert_eq!(pages_with(&report, Kind::EmptyBody), vec![2]);
+        assert!(report.void().is_empty());
+    }
+
+    #[test]
+    fn page_label_test() {
+        let page = |label: &str| {
+            show(&[
+                (72f64, 760f64, "AKESO Inc."),
+                (450f64, 760f64, label),
+                (72f64, 720f64, "Subject"),
+                (200f64, 720f64, "Age"),
+                (72f64, 700f64, "01001"),
+                (200f64, 700f64, "45"),
+            ])
+        };
+        let data = pdf(&[
+            page("Page 1 of 3"),
+            page("Page 3 of 3"),
+            page("Page 3 of 3"),
+        ]);
+        let report = probe_bytes("labels.pdf", &data).unwrap();
+        assert!(report.void().is_empty());
+        assert_eq!(pages_with(&report, Kind::PageNumber), vec![2, 3]);
+        assert!(pages_with(&report, Kind::PageCount).is_empty());
+        // the sponsor line holding the label is no column header
+        assert!(pages_with(&report, Kind::ColumnHeader).is_empty());
+        assert_eq!(report.body_rows(), vec![1, 1, 1]);
+
+        // a study printing the label in its own words
+        let data = pdf(&[
+            page("Seite 1 von 2"),
+            page("Seite 2 von 2"),
+            page("Seite 3 von 2"),
+        ]);
+        let report = Probe::default()
+            .set_page_labels(&[r"Seite (\d+) von (\d+)"])
+            .unwrap()
+            .probe_bytes("labels.pdf", &data)
+            .unwrap();
+        assert_eq!(pages_with(&report, Kind::PageCount), vec![1, 2, 3]);
+        assert!(Probe::default().set_page_labels(&["(unclosed"]).is_err());
+    }
+
+    #[test]
+    fn title_block_test() {
+        let page = |label: &str, population: &str| {
+            let title = match label {
+                "Page 1 of 3" => "Table 14.1.1 Demographics",
+                _ => "Table 14.1.1 Demographics (continued)",
+            };
+            show(&[
+                (72f64, 760f64, "AKESO Inc."),
+                (450f64, 760f64, label),
+                (72f64, 745f64, title),
+                (72f64, 730f64, population),
+                (72f64, 710f64, "Subject"),
+                (200f64, 710f64, "Age"),
+                (72f64, 695f64, "01001"),
+                (200f64, 695f64, "45"),
+            ]) + "72 722 m 520 722 l S 72 704 m 520 704 l S"
+        };
+        let data = pdf(&[
+            page("Page 1 of 3", "Safety Set"),
+            page("Page 2 of 3", "Safety Set"),
+            page("Page 3 of 3", "Full Analysis Set"),
+        ]);
+        let report = probe_bytes("titles.pdf", &data).unwrap();
+        assert_eq!(pages_with(&report, Kind::Title), vec![3]);
+    }
+
+    #[test]
+    fn column_header_test() {
+        let title = (72f64, 760f64, "Listing 16.2.1");
+        let rules = "72 722 m 320 722 l S 72 704 m 320 704 l S";
+        let data = pdf(&[
+            show(&[
+                title,
+                (72f64, 710f64, "Subject"),
+                (200f64, 710f64, "Age"),
+                (72f64, 695f64, "01001"),
+                (200f64, 695f64, "45"),
+            ]) + rules,
+            // the header row did not repeat, the body starts right below the title
+            show(&[
+                title,
+                (72f64, 710f64, "01002"),
+                (200f64, 710f64, "51"),
+                (72f64, 695f64, "01003"),
+                (200f64, 695f64, "38"),
+            ]),
+        ]);
+        let report = probe_bytes("header.pdf", &data).unwrap();
+        assert_eq!(pages_with(&report, Kind::ColumnHeader), vec![2]);
+    }
+
+    #[test]
+    fn footnote_test() {
+        let rules = "72 722 m 320 722 l S 72 704 m 320 704 l S 72 680 m 320 680 l S";
+        let table = [
+            (72f64, 760f64, "Table 14.1.1"),
+            (72f64, 710f64, "Subject"),
+            (200f64, 710f64, "Age"),
+            (72f64, 695f64, "01001"),
+            (200f64, 695f64, "45"),
+        ];
+        let note = (72f64, 660f64, "Note: age at screening.");
+        let data = pdf(&[
+            show(&table) + rules,
+            show(&table) + rules,
+            show(&[table[0], note]),
+        ]);
+        let pages = |placement: FootnotePlacement, kind: Kind| {
+            let probe = Probe::default().set_footnote_placement(placement);
+            pages_with(&probe.probe_bytes("footnote.pdf", &data).unwrap(), kind)
+        };
+        assert_eq!(
+            pages(FootnotePlacement::LastPage, Kind::FootnotePage),
+            vec![3]
+        );
+        assert!(pages(FootnotePlacement::LastPage, Kind::FootnoteMissing).is_empty());
+        assert_eq!(
+            pages(FootnotePlacement::EveryPage, Kind::FootnoteMissing),
+            vec![1, 2]
+        );
+        assert!(Probe::default().set_footnotes(&["["]).is_err());
+
+        // a table continued on the next page has no closing rule above its footnotes
+        let open = |subjects: [&str; 2]| {
+            show(&[
+                table[0],
+                table[1],
+                table[2],
+                (72f64, 695f64, subjects[0]),
+                (200f64, 695f64, "45"),
+                (72f64, 680f64, subjects[1]),
+                (200f64, 680f64, "51"),
+                note,
+            ]) + "72 722 m 320 722 l S 72 704 m 320 704 l S"
+        };
+        let data = pdf(&[open(["01001", "01002"]), open(["01003", "01004"])]);
+        let report = Probe::default()
+            .set_sort_columns(&[0])
+            .probe_bytes("footnote.pdf", &data)
+            .unwrap();
+        assert!(report.findings().is_empty());
+        assert_eq!(report.body_rows(), vec![2, 2]);
+    }
+
+    #[test]
+    fn fill_test() {
+        let rules = "72 722 m 320 722 l S 72 704 m 320 704 l S";
+        let page = |rows: usize| {
+            let mut texts = vec![
+                (72f64, 760f64, "Listing 16.2.1"),
+                (72f64, 710f64, "Subject"),
+                (200f64, 710f64, "Age"),
+            ];
+            for i in 0..rows {
+                let y = 690f64 - i as f64 * 15f64;
+                texts.push((72f64, y, "01001"));
+                texts.push((200f64, y, "45"));
+            }
+            show(&texts) + rules
+        };
+        let data = pdf(&[page(6), page(6), page(3), page(5)]);
+        let report = probe_bytes("fill.pdf", &data).unwrap();
+        assert!(report.findings().is_empty());
+        let underfull = Probe::default().set_underfull_ratio(0.6);
+        let underfull = underfull.probe_bytes("fill.pdf", &data).unwrap();
+        assert_eq!(underfull.findings().len(), 1);
+        assert_eq!(pages_with(&underfull, Kind::Underfull), vec![3]);
+        let orphans = Probe::default().set_orphan_rows(3);
+        let orphans = orphans.probe_bytes("fill.pdf", &data).unwrap();
+        assert_eq!(orphans.findings().len(), 1);
+        assert_eq!(pages_with(&orphans, Kind::OrphanRows), vec![3]);
+        assert_eq!(report.body_rows(), vec![6, 6, 3, 5]);
+    }
+
+    #[test]
+    fn group_test() {
+        let rules = "72 722 m 420 722 l S 72 704 m 420 704 l S 72 680 m 420 680 l S";
+        let page = |title: &str, subject: &str| {
+            show(&[
+                (72f64, 760f64, title),
+                (72f64, 710f64, "Subject"),
+                (200f64, 710f64, "Term"),
+                (320f64, 710f64, "Grade"),
+                (72f64, 690f64, subject),
+                (200f64, 690f64, "Nausea"),
+                (320f64, 690f64, "1"),
+            ]) + rules
+        };
+        let title = "Listing 16.2.7 Adverse Events";
+        let data = pdf(&[
+            page(title, "01001"),
+            page("Listing 16.2.7 Adverse Events (continued)", ""),
+            page(title, ""),
+        ]);
+        let pages = |probe: Probe| {
+            pages_with(
+                &probe.probe_bytes("group.pdf", &data).unwrap(),
+                Kind::GroupKey,
+            )
+        };
+        let subject = Probe::default().set_key_columns(&[0]);
+        assert_eq!(pages(subject), vec![3]);
+        // the continued title is the same title
+        let report = probe_bytes("group.pdf", &data).unwrap();
+        assert!(pages_with(&report, Kind::Title).is_empty());
+        let continued = Probe::default()
+            .set_key_columns(&[0])
+            .set_continued(&["^$"])
+            .unwrap();
+        assert_eq!(pages(continued), vec![2, 3]);
+        assert!(pages(Probe::default()).is_empty());
+    }
+
+    #[test]
+    fn sort_order_test() {
+        let rules = "72 722 m 320 722 l S 72 704 m 320 704 l S";
+        let page = |subjects: [&str; 2]| {
+            show(&[
+                (72f64, 760f64, "Listing 16.2.1"),
+                (72f64, 710f64, "Subject"),
+                (200f64, 710f64, "Age"),
+                (72f64, 690f64, subjects[0]),
+                (200f64, 690f64, "45"),
+                (72f64, 675f64, subjects[1]),
+                (200f64, 675f64, "51"),
+            ]) + rules
+        };
+        let data = pdf(&[
+            page(["01001", "01002"]),
+            page(["01005", "01006"]),
+            page(["01003", "01004"]),
+        ]);
+        let pages = |probe: Probe| {
+            pages_with(
+                &probe.probe_bytes("sort.pdf", &data).unwrap(),
+                Kind::SortOrder,
+            )
+        };
+        assert!(pages(Probe::default()).is_empty());
+        assert_eq!(pages(Probe::default().set_sort_columns(&[0])), vec![3]);
+    }
+
+    #[test]
+    fn overflow_test() {
+        let data = pdf(&[show(&[
+            (72f64, 760f64, "AKESO Inc."),
+            (72f64, 720f64, "Subject"),
+            (200f64, 720f64, "Age"),
+            (72f64, 700f64, "01001"),
+            (200f64, 700f64, "45"),
+        ])]);
+        let pages = |probe: Probe| {
+            pages_with(
+                &probe.probe_bytes("overflow.pdf", &data).unwrap(),
+                Kind::Overflow,
+            )
+        };
+        assert!(pages(Probe::default()).is_empty());
+        assert_eq!(
+            pages(Probe::default().set_margins(72f64, 72f64, 72f64, 36f64)),
+            vec![1]
+        );
+    }
 }
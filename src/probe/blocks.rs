@@ -0,0 +1,141 @@
+use regex::Regex;
+
+use crate::pdf::reader::{closing_rule, Cell, Rule};
+
+/// The rows of a page split into the blocks of a TFL output
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Blocks {
+    /// rows above the column header, such as sponsor line and titles
+    pub title: Vec<Vec<Cell>>,
+    pub header: Vec<Vec<Cell>>,
+    pub body: Vec<Vec<Cell>>,
+    /// rows below the rule closing the table, such as footnotes
+    pub foot: Vec<Vec<Cell>>,
+    /// whether a rule closes the table below its body, a table without one goes on over the page
+    pub closed: bool,
+}
+
+/// split the table of a page at its header rows and closing rule, a page without header rows is
+/// all title, the footnotes of a table without closing rule are told from its body by their text
+pub fn split_blocks(table: Vec<Vec<Cell>>, rules: &[Rule], footnotes: &[Regex]) -> Blocks {
+    let first = table
+        .iter()
+        .position(|row| row.iter().any(|cell| cell.header));
+    let last = table
+        .iter()
+        .rposition(|row| row.iter().any(|cell| cell.header));
+    let (first, last) = match (first, last) {
+        (Some(first), Some(last)) => (first, last),
+        _ => {
+            return Blocks {
+                title: table,
+                ..Blocks::default()
+            }
+        }
+    };
+    let bottom = closing_rule(&table, rules).map(|rule| rule.y);
+    let mut blocks = Blocks {
+        closed: bottom.is_some(),
+        ..Blocks::default()
+    };
+    for (i, row) in table.into_iter().enumerate() {
+        if i < first {
+            blocks.title.push(row);
+        } else if i <= last {
+            blocks.header.push(row);
+        } else if bottom.is_some_and(|y| row.first().is_some_and(|cell| cell.y < y)) {
+            blocks.foot.push(row);
+        } else {
+            blocks.body.push(row);
+        }
+    }
+    if !blocks.closed {
+        let start = footnote_start(&blocks.body, footnotes);
+        blocks.foot = blocks.body.split_off(start);
+    }
+    blocks
+}
+
+/// the first of the trailing rows which look like footnotes, rows of a single cell between them
+/// are taken for footnotes running on over several lines
+fn footnote_start(rows: &[Vec<Cell>], footnotes: &[Regex]) -> usize {
+    let mut start = rows.len();
+    for (i, row) in rows.iter().enumerate().rev() {
+        let text = row_text(row);
+        if footnotes.iter().any(|footnote| footnote.is_match(&text)) {
+            start = i;
+        } else if row.len() > 1 {
+            break;
+        }
+    }
+    start
+}
+
+/// the text of a row with its cells and words separated by a single space
+pub fn row_text(row: &[Cell]) -> String {
+    row.iter()
+        .flat_map(|cell| cell.text.split_whitespace())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// the text of rows with the matches of masks such as page labels taken out, rows left empty are
+/// dropped
+pub fn masked_rows(rows: &[Vec<Cell>], masks: &[Regex]) -> Vec<String> {
+    rows.iter()
+        .map(|row| {
+            let text = masks.iter().fold(row_text(row), |text, mask| {
+                mask.replace_all(&text, "").to_string()
+            });
+            text.split_whitespace().collect::<Vec<_>>().join(" ")
+        })
+        .filter(|row| !row.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod blocks_test {
+    use super::*;
+    use crate::probe::test_util::cell;
+
+    #[test]
+    fn split_blocks_test() {
+        let table = vec![
+            vec![cell("Table 14.1", 0, 760f64, false)],
+            vec![
+                cell("Subject", 0, 720f64, true),
+                cell("Age", 1, 720f64, true),
+            ],
+            vec![
+                cell("01001", 0, 690f64, false),
+                cell("45", 1, 690f64, false),
+            ],
+            vec![cell("Note: years", 0, 650f64, false)],
+        ];
+        let rule = |y: f64| Rule {
+            x: 72f64,
+            end_x: 222f64,
+            y,
+        };
+        let footnotes = [Regex::new(r"^Note:").unwrap()];
+        let rules = [rule(740f64), rule(700f64), rule(670f64)];
+        let blocks = split_blocks(table.clone(), &rules, &footnotes);
+        assert_eq!(blocks.title.len(), 1);
+        assert_eq!(blocks.header.len(), 1);
+        assert_eq!(row_text(&blocks.body[0]), "01001 45");
+        assert_eq!(blocks.foot.len(), 1);
+        assert!(blocks.closed);
+
+        // without a closing rule the table runs on to the footnotes
+        let mut open = table.clone();
+        open.push(vec![cell("from birth", 0, 640f64, false)]);
+        let blocks = split_blocks(open.clone(), &rules[..2], &footnotes);
+        assert_eq!(blocks.body.len(), 1);
+        assert_eq!(blocks.foot.len(), 2);
+        assert!(!blocks.closed);
+        let blocks = split_blocks(open, &rules[..2], &[]);
+        assert_eq!((blocks.body.len(), blocks.foot.len()), (3, 0));
+        let blocks = split_blocks(table[..1].to_vec(), &[], &footnotes);
+        assert_eq!((blocks.title.len(), blocks.header.len()), (1, 0));
+    }
+}
@@ -0,0 +1,97 @@
+use regex::Regex;
+
+use crate::report::{Kind, Report};
+
+use super::blocks::{self, Blocks};
+
+/// whether nothing lies below the column header of a page, a page without column header such as
+/// a figure or a "no subjects" page is never empty
+pub fn body_empty(blocks: &Blocks) -> bool {
+    !blocks.header.is_empty() && blocks.body.is_empty()
+}
+
+/// report pages without column header which hold nothing but rows of the title block of the
+/// output, the first one in titles, pages drawing a figure are left out
+pub fn check_title_only(
+    pages: &[Blocks],
+    titles: &[Option<Vec<String>>],
+    figures: &[bool],
+    masks: &[Regex],
+    report: &Report,
+) {
+    let title = match titles.iter().flatten().next() {
+        Some(title) => title,
+        None => return,
+    };
+    for (i, (blocks, figure)) in pages.iter().zip(figures).enumerate() {
+        if !blocks.header.is_empty() || *figure {
+            continue;
+        }
+        let rows = blocks::masked_rows(&blocks.title, masks);
+        if !rows.is_empty() && rows.iter().all(|row| title.contains(row)) {
+            report.append_finding(i + 1, Kind::EmptyBody, "nothing below the title block");
+        }
+    }
+}
+
+#[cfg(test)]
+mod body_test {
+    use super::*;
+    use crate::probe::test_util::{page, pages_with, row};
+
+    #[test]
+    fn body_empty_test() {
+        assert!(body_empty(&page(&["Subject"], &[])));
+        assert!(!body_empty(&page(&["Subject"], &[&["01001"]])));
+
+        // a one column page or a figure has no column header
+        let no_header = Blocks {
+            title: vec![row(&["No subjects met the criteria."], false)],
+            ..Blocks::default()
+        };
+        assert!(!body_empty(&no_header));
+        assert!(!body_empty(&Blocks::default()));
+    }
+
+    #[test]
+    fn check_title_only_test() {
+        let labels = [Regex::new(r"(?i)\bpage\s+(\d+)\s+of\s+(\d+)").unwrap()];
+        let title_only = |rows: &[&[&str]]| Blocks {
+            title: rows.iter().map(|texts| row(texts, false)).collect(),
+            ..Blocks::default()
+        };
+        let pages = vec![
+            page(&["Subject"], &[&["01001"]]),
+            title_only(&[&["AKESO Inc.", "Page 2 of 4"], &["Table 14.1.1"]]),
+            title_only(&[&["AKESO Inc."], &["No subjects met the criteria."]]),
+            title_only(&[&["AKESO Inc."], &["Figure 14.2.1"]]),
+        ];
+        let titles = [
+            Some(vec!["AKESO Inc.".to_string(), "Table 14.1.1".to_string()]),
+            None,
+            None,
+            None,
+        ];
+        let report = Report::new("t.pdf");
+        check_title_only(&pages, &titles, &[false; 4], &labels, &report);
+        assert_eq!(pages_with(&report, Kind::EmptyBody), vec![2]);
+
+        // a figure drawn below the title
+        let report = Report::new("t.pdf");
+        check_title_only(
+            &pages,
+            &titles,
+            &[false, true, false, false],
+            &labels,
+            &report,
+        );
+        check_title_only(
+            &pages,
+            &[None, None, None, None],
+            &[false; 4],
+            &labels,
+            &report,
+        );
+        assert!(report.findings().is_empty());
+    }
+}
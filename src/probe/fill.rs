@@ -0,0 +1,122 @@
+use crate::report::{Kind, Report};
+
+use super::blocks::Blocks;
+
+/// Thresholds of the body rows a page is expected to hold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fill {
+    /// pages holding less than this share of the typical body rows are underfull
+    pub ratio: f64,
+    /// pages after the first holding this many body rows or fewer hold orphan rows
+    pub orphan_rows: usize,
+}
+
+/// the median of the body rows on the table pages before the last one, which is expected to be
+/// short
+fn typical_rows(rows: &[Option<usize>]) -> Option<f64> {
+    let mut counts = rows
+        .iter()
+        .take(rows.len().saturating_sub(1))
+        .flatten()
+        .copied()
+        .collect::<Vec<_>>();
+    if counts.is_empty() {
+        return None;
+    }
+    counts.sort_unstable();
+    let middle = counts.len() / 2;
+    Some(if counts.len() % 2 == 0 {
+        (counts[middle - 1] + counts[middle]) as f64 / 2f64
+    } else {
+        counts[middle] as f64
+    })
+}
+
+/// record the body rows of every page and report the pages well below the typical fill of the
+/// output, the last page is only held to the orphan rows
+pub fn check_fill(pages: &[Blocks], fill: Fill, report: &Report) {
+    let rows = pages
+        .iter()
+        .map(|blocks| (!blocks.header.is_empty()).then_some(blocks.body.len()))
+        .collect::<Vec<_>>();
+    for blocks in pages {
+        report.append_body_rows(blocks.body.len());
+    }
+    let typical = match typical_rows(&rows) {
+        Some(typical) => typical,
+        None => return,
+    };
+    let first = rows.iter().position(Option::is_some);
+    for (i, count) in rows.iter().enumerate() {
+        let count = match count {
+            Some(count) if *count > 0 => *count,
+            _ => continue,
+        };
+        let page = i + 1;
+        let last = page == pages.len();
+        if first < Some(i) && count <= fill.orphan_rows && typical > fill.orphan_rows as f64 {
+            report.append_finding(
+                page,
+                Kind::OrphanRows,
+                &format!("{} body rows left over after the break", count),
+            );
+        } else if !last && (count as f64) < fill.ratio * typical {
+            report.append_finding(
+                page,
+                Kind::Underfull,
+                &format!(
+                    "{} body rows, pages of the output hold {} typically",
+                    count, typical
+                ),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod fill_test {
+    use super::*;
+    use crate::probe::test_util::{self, row};
+
+    fn page(body: usize) -> Blocks {
+        Blocks {
+            body: vec![row(&["01001"], false); body],
+            ..test_util::page(&["Subject"], &[])
+        }
+    }
+
+    #[test]
+    fn check_fill_test() {
+        let fill = Fill {
+            ratio: 0.5,
+            orphan_rows: 2,
+        };
+        let report = Report::new("t.pdf");
+        check_fill(
+            &[page(40), page(38), page(12), page(41), page(2), page(1)],
+            fill,
+            &report,
+        );
+        assert_eq!(report.body_rows(), vec![40, 38, 12, 41, 2, 1]);
+        let findings = report
+            .findings()
+            .iter()
+            .map(|f| (f.page, f.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            findings,
+            vec![
+                (3, Kind::Underfull),
+                (5, Kind::OrphanRows),
+                (6, Kind::OrphanRows)
+            ]
+        );
+
+        // a short last page is expected, so is a table fitting one page
+        let report = Report::new("t.pdf");
+        check_fill(&[page(40), page(40), page(9)], fill, &report);
+        check_fill(&[page(1)], fill, &report);
+        assert!(report.findings().is_empty());
+        assert_eq!(typical_rows(&[Some(3), None, Some(5), Some(1)]), Some(4f64));
+    }
+}
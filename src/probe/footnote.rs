@@ -0,0 +1,187 @@
+use regex::Regex;
+
+use crate::report::{Kind, Report};
+
+use super::{
+    blocks::{self, Blocks},
+    title,
+};
+
+/// Where the study convention puts the footnotes of an output
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FootnotePlacement {
+    /// below the table on every page
+    EveryPage,
+    /// below the table on the last page only
+    LastPage,
+}
+
+/// the footnote rows of a page with the masks taken out and whether the page holds nothing
+/// else, a page without table holds only footnotes when each of its rows is either a row of the
+/// output title or looks like a footnote
+fn page_footnotes(
+    blocks: &Blocks,
+    title: &[String],
+    patterns: &[Regex],
+    masks: &[Regex],
+) -> (Vec<String>, bool) {
+    if !blocks.header.is_empty() || !blocks.body.is_empty() {
+        return (blocks::masked_rows(&blocks.foot, masks), false);
+    }
+    let rows = blocks::masked_rows(&blocks.title, masks);
+    let footnotes = rows
+        .iter()
+        .filter(|row| !title.contains(row))
+        .cloned()
+        .collect::<Vec<_>>();
+    let only = !footnotes.is_empty()
+        && footnotes
+            .iter()
+            .all(|row| patterns.iter().any(|pattern| pattern.is_match(row)));
+    if only {
+        (footnotes, true)
+    } else {
+        (vec![], false)
+    }
+}
+
+/// report footnote-only pages, pages missing the footnotes the placement puts on them and
+/// footnotes whose text differs from the first page with footnotes, an output without any
+/// footnotes is left alone
+pub fn check_footnotes(
+    pages: &[Blocks],
+    placement: FootnotePlacement,
+    patterns: &[Regex],
+    masks: &[Regex],
+    report: &Report,
+) {
+    let title = pages
+        .iter()
+        .find_map(|blocks| title::title_block(blocks, masks))
+        .unwrap_or_default();
+    let footnotes = pages
+        .iter()
+        .map(|blocks| page_footnotes(blocks, &title, patterns, masks))
+        .collect::<Vec<_>>();
+    let first = match footnotes.iter().position(|(rows, _)| !rows.is_empty()) {
+        Some(first) => first,
+        None => return,
+    };
+    for (i, (rows, only)) in footnotes.iter().enumerate() {
+        let page = i + 1;
+        if *only {
+            report.append_finding(page, Kind::FootnotePage, "page holds footnotes only");
+        }
+        let expected = match placement {
+            FootnotePlacement::EveryPage => !pages[i].header.is_empty() || *only,
+            FootnotePlacement::LastPage => page == pages.len(),
+        };
+        if rows.is_empty() {
+            if expected {
+                report.append_finding(page, Kind::FootnoteMissing, "no footnotes");
+            }
+            continue;
+        }
+        if let Some(row) = rows.iter().find(|row| !footnotes[first].0.contains(row)) {
+            report.append_finding(
+                page,
+                Kind::FootnoteText,
+                &format!("footnote \"{}\" not found on page {}", row, first + 1),
+            );
+        } else if rows.len() < footnotes[first].0.len() {
+            report.append_finding(
+                page,
+                Kind::FootnoteText,
+                &format!("footnotes of page {} incomplete", first + 1),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod footnote_test {
+    use super::*;
+    use crate::{pdf::reader::Cell, probe::test_util};
+
+    fn rows(texts: &[&str]) -> Vec<Vec<Cell>> {
+        texts
+            .iter()
+            .map(|text| test_util::row(&[text], false))
+            .collect()
+    }
+
+    fn page(foot: &[&str]) -> Blocks {
+        Blocks {
+            title: rows(&["Table 14.1.1 Page 1 of 3"]),
+            foot: rows(foot),
+            ..test_util::page(&["Subject"], &[&["01001"]])
+        }
+    }
+
+    fn kinds(report: &Report) -> Vec<(usize, Kind)> {
+        report.findings().iter().map(|f| (f.page, f.kind)).collect()
+    }
+
+    #[test]
+    fn check_footnotes_test() {
+        let patterns = [Regex::new(r"(?i)^notes?:").unwrap()];
+        let labels = [Regex::new(r"(?i)\bpage\s+(\d+)\s+of\s+(\d+)").unwrap()];
+        let footnote_only = Blocks {
+            title: rows(&["Table 14.1.1 Page 3 of 3", "Note: age at screening."]),
+            ..Blocks::default()
+        };
+        let pages = vec![
+            page(&["Note: age at screening.", "Program: t_dm.sas"]),
+            page(&["Note: age at screening."]),
+            page(&[]),
+            footnote_only.clone(),
+        ];
+        let report = Report::new("t.pdf");
+        check_footnotes(
+            &pages,
+            FootnotePlacement::EveryPage,
+            &patterns,
+            &labels,
+            &report,
+        );
+        assert_eq!(
+            kinds(&report),
+            vec![
+                (2, Kind::FootnoteText),
+                (3, Kind::FootnoteMissing),
+                (4, Kind::FootnotePage),
+                (4, Kind::FootnoteText),
+            ]
+        );
+
+        let pages = vec![page(&[]), page(&[]), footnote_only];
+        let report = Report::new("t.pdf");
+        check_footnotes(
+            &pages,
+            FootnotePlacement::LastPage,
+            &patterns,
+            &labels,
+            &report,
+        );
+        assert_eq!(kinds(&report), vec![(3, Kind::FootnotePage)]);
+
+        let report = Report::new("t.pdf");
+        check_footnotes(
+            &[page(&["Note: x"]), page(&[])],
+            FootnotePlacement::LastPage,
+            &patterns,
+            &labels,
+            &report,
+        );
+        assert_eq!(kinds(&report), vec![(2, Kind::FootnoteMissing)]);
+        let report = Report::new("t.pdf");
+        check_footnotes(
+            &[page(&[]), page(&[])],
+            FootnotePlacement::EveryPage,
+            &patterns,
+            &labels,
+            &report,
+        );
+        assert!(report.findings().is_empty());
+    }
+}
@@ -0,0 +1,127 @@
+use regex::Regex;
+
+use crate::{
+    pdf::reader::Cell,
+    report::{Kind, Report},
+};
+
+use super::{
+    blocks::{self, Blocks},
+    split,
+};
+
+/// the text of the key columns of a row, None when all of them are blank
+pub fn row_key(row: &[Cell], key_columns: &[usize]) -> Option<Vec<String>> {
+    let key = key_columns
+        .iter()
+        .map(|column| {
+            row.iter()
+                .filter(|cell| cell.column == *column)
+                .map(|cell| cell.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>();
+    key.iter().any(|text| !text.is_empty()).then_some(key)
+}
+
+/// report continuation pages which start inside the group left open by the previous page
+/// without repeating its key or marking the group as continued, a page starting with a split row
+/// is left to the split row check
+pub fn check_groups(pages: &[Blocks], key_columns: &[usize], markers: &[Regex], report: &Report) {
+    if key_columns.is_empty() {
+        return;
+    }
+    let mut group: Option<Vec<String>> = None;
+    for (i, blocks) in pages.iter().enumerate() {
+        let first = match blocks.body.first() {
+            Some(first) => first,
+            None => continue,
+        };
+        let marked = blocks
+            .title
+            .iter()
+            .chain(&blocks.header)
+            .chain(std::iter::once(first))
+            .any(|row| {
+                let text = blocks::row_text(row);
+                markers.iter().any(|marker| marker.is_match(&text))
+            });
+        let split = i > 0 && split::split_row(&pages[i - 1], blocks).is_some();
+        if let (Some(key), false) = (&group, split) {
+            let open = row_key(first, key_columns).is_none();
+            if open && !marked {
+                report.append_finding(
+                    i + 1,
+                    Kind::GroupKey,
+                    &format!(
+                        "page starts inside group \"{}\" of page {} without repeating its key",
+                        key.join(" "),
+                        i
+                    ),
+                );
+            }
+        }
+        if let Some(key) = blocks
+            .body
+            .iter()
+            .rev()
+            .find_map(|row| row_key(row, key_columns))
+        {
+            group = Some(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod group_test {
+    use super::*;
+    use crate::probe::test_util::page;
+
+    #[test]
+    fn check_groups_test() {
+        let markers = [Regex::new(r"(?i)\(cont(inued|\.)?\)").unwrap()];
+        let header = ["Arm", "Subject", "Term", "Grade"];
+        let pages = vec![
+            page(
+                &header,
+                &[&["A", "01001", "Nausea", "1"], &["", "", "Rash", "2"]],
+            ),
+            // orphaned rows of subject 01001
+            page(&header, &[&["", "", "Fever", "1"]]),
+            // marked as continued
+            page(&header, &[&["", "(cont.)", "Cough", "1"]]),
+            page(&header, &[&["B", "01002", "Cough", "1"]]),
+        ];
+        let report = Report::new("t.pdf");
+        check_groups(&pages, &[0, 1], &markers, &report);
+        let findings = report.findings();
+        assert_eq!(findings.len(), 1);
+        assert_eq!((findings[0].page, findings[0].kind), (2, Kind::GroupKey));
+        assert!(findings[0].message.contains("\"A 01001\""));
+
+        // arm is printed once per group, the subject on every row
+        let report = Report::new("t.pdf");
+        check_groups(&pages, &[1], &markers, &report);
+        assert_eq!(report.findings().len(), 1);
+        check_groups(&pages, &[], &markers, &report);
+        assert_eq!(report.findings().len(), 1);
+        assert_eq!(
+            row_key(&pages[0].body[0], &[1, 2]),
+            Some(vec!["01001".to_string(), "Nausea".to_string()])
+        );
+        assert_eq!(row_key(&pages[0].body[1], &[0, 1]), None);
+
+        // the rest of a row broken over an open table is a split row, not an orphaned group
+        let pages = vec![
+            Blocks {
+                closed: false,
+                ..page(&header, &[&["A", "01001", "Nausea"]])
+            },
+            page(&header, &[&["", "", "", "1"]]),
+        ];
+        let report = Report::new("t.pdf");
+        check_groups(&pages, &[0, 1], &markers, &report);
+        assert!(report.findings().is_empty());
+    }
+}
@@ -0,0 +1,76 @@
+use crate::report::{Kind, Report};
+
+use super::blocks::{self, Blocks};
+
+/// check that the column header rows of the first page with a table repeat on every following
+/// page, a page whose header rows share no row with them lost its header
+pub fn check_headers(pages: &[Blocks], report: &Report) {
+    let headers = pages
+        .iter()
+        .map(|blocks| {
+            blocks
+                .header
+                .iter()
+                .map(|row| blocks::row_text(row))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let first_page = match headers.iter().position(|header| !header.is_empty()) {
+        Some(first_page) => first_page,
+        None => return,
+    };
+    let first = &headers[first_page];
+    for (i, header) in headers.iter().enumerate().skip(first_page + 1) {
+        if header == first {
+            continue;
+        }
+        let message = if !header.iter().any(|row| first.contains(row)) {
+            format!("column header of page {} does not repeat", first_page + 1)
+        } else {
+            match first.iter().find(|row| !header.contains(row)) {
+                Some(row) => format!("column header row \"{}\" missing", row),
+                None => format!(
+                    "column header other than on page {}: \"{}\"",
+                    first_page + 1,
+                    header.join(" / ")
+                ),
+            }
+        };
+        report.append_finding(i + 1, Kind::ColumnHeader, &message);
+    }
+}
+
+#[cfg(test)]
+mod header_test {
+    use super::*;
+    use crate::probe::test_util::{pages_with, row};
+
+    fn header(rows: &[&[&str]]) -> Blocks {
+        Blocks {
+            header: rows.iter().map(|texts| row(texts, true)).collect(),
+            ..Blocks::default()
+        }
+    }
+
+    #[test]
+    fn check_headers_test() {
+        let report = Report::new("t.pdf");
+        let treatment: &[&str] = &["Treatment"];
+        let arms: &[&str] = &["Subject", "Arm A", "Arm B"];
+        check_headers(
+            &[
+                Blocks::default(),
+                header(&[treatment, arms]),
+                header(&[treatment, arms]),
+                header(&[arms]),
+                header(&[&["01001", "12", "7"]]),
+                Blocks::default(),
+            ],
+            &report,
+        );
+        assert_eq!(pages_with(&report, Kind::ColumnHeader), vec![4, 5, 6]);
+        let findings = report.findings();
+        assert!(findings[0].message.contains("\"Treatment\""));
+        assert!(findings[1].message.contains("page 2 does not repeat"));
+    }
+}
@@ -0,0 +1,94 @@
+use regex::Regex;
+
+use crate::report::{Kind, Report};
+
+/// page number and page count of the first page label found in the rows of a page
+pub fn page_label(rows: &[String], patterns: &[Regex]) -> Option<(usize, usize)> {
+    rows.iter().find_map(|row| {
+        patterns.iter().find_map(|pattern| {
+            let captures = pattern.captures(row)?;
+            let number = captures.get(1)?.as_str().parse().ok()?;
+            let count = captures.get(2)?.as_str().parse().ok()?;
+            Some((number, count))
+        })
+    })
+}
+
+/// check that page numbers go up by one from the first page and that the page count is the number
+/// of pages of the output, an output printing no page labels at all is skipped
+pub fn check_labels(labels: &[Option<(usize, usize)>], report: &Report) {
+    if labels.iter().all(Option::is_none) {
+        return;
+    }
+    let pages = labels.len();
+    let mut previous: Option<usize> = None;
+    for (i, label) in labels.iter().enumerate() {
+        let page = i + 1;
+        let (number, count) = match label {
+            Some(label) => *label,
+            None => {
+                report.append_finding(page, Kind::PageNumber, "no page label");
+                previous = None;
+                continue;
+            }
+        };
+        let expected = previous.map_or(page, |previous| previous + 1);
+        if number != expected {
+            report.append_finding(
+                page,
+                Kind::PageNumber,
+                &format!("page label numbers page {}, expected {}", number, expected),
+            );
+        }
+        if count != pages {
+            report.append_finding(
+                page,
+                Kind::PageCount,
+                &format!(
+                    "page label counts {} pages, the output has {}",
+                    count, pages
+                ),
+            );
+        }
+        previous = Some(number);
+    }
+}
+
+#[cfg(test)]
+mod numbering_test {
+    use super::*;
+    use crate::probe::test_util::pages_with;
+
+    #[test]
+    fn page_label_test() {
+        let patterns = [
+            Regex::new(r"(?i)\bpage\s+(\d+)\s+of\s+(\d+)").unwrap(),
+            Regex::new(r"第\s*(\d+)\s*页\s*[,，/]?\s*共\s*(\d+)\s*页").unwrap(),
+        ];
+        let rows = vec!["AKESO Inc. Page 2 of 10".to_string()];
+        assert_eq!(page_label(&rows, &patterns), Some((2, 10)));
+        let rows = vec!["Table 14.1".to_string(), "第 3 页，共 10 页".to_string()];
+        assert_eq!(page_label(&rows, &patterns), Some((3, 10)));
+        assert_eq!(page_label(&["Homepage 1".to_string()], &patterns), None);
+    }
+
+    #[test]
+    fn check_labels_test() {
+        let report = Report::new("t.pdf");
+        check_labels(&[Some((1, 3)), Some((2, 3)), Some((3, 3))], &report);
+        assert!(report.findings().is_empty());
+
+        // pages two and three swapped, and a page without label
+        check_labels(&[Some((1, 4)), Some((3, 4)), Some((2, 4)), None], &report);
+        assert_eq!(pages_with(&report, Kind::PageNumber), vec![2, 3, 4]);
+        assert_eq!(report.findings().len(), 3);
+
+        let report = Report::new("t.pdf");
+        check_labels(&[Some((1, 2)), Some((2, 3)), Some((3, 3))], &report);
+        let findings = report.findings();
+        assert_eq!(findings.len(), 1);
+        assert_eq!((findings[0].page, findings[0].kind), (1, Kind::PageCount));
+        check_labels(&[None, None], &report);
+        assert_eq!(report.findings().len(), 1);
+    }
+}
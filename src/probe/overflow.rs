@@ -0,0 +1,196 @@
+use crate::{
+    pdf::reader::{closing_rule, Cell, Page, Rule, TextRun},
+    report::{Kind, Report},
+};
+
+// text may pass a bound by this much before it overflows
+const OVERFLOW_TOLERANCE: f64 = 0.5;
+// rules at least this share of the longest rule frame the table
+const FRAME_RATIO: f64 = 0.5;
+// share of the font size glyphs rise above the baseline
+const CAP_HEIGHT_RATIO: f64 = 0.7;
+
+/// bounds of a run as `[llx lly urx ury]`, glyphs are taken to rise by the cap height
+fn run_bounds(run: &TextRun) -> [f64; 4] {
+    [
+        run.x.min(run.end_x),
+        run.y.min(run.end_y),
+        run.x.max(run.end_x),
+        run.y.max(run.end_y) + CAP_HEIGHT_RATIO * run.size,
+    ]
+}
+
+/// the table frame as `[llx lly urx ury]` spanned by the long rules of the page and whether a rule
+/// closes it, the frame of a table without closing rule reaches down to its last row
+fn table_frame(table: &[Vec<Cell>], rules: &[Rule]) -> Option<([f64; 4], bool)> {
+    let longest = rules
+        .iter()
+        .map(|rule| rule.end_x - rule.x)
+        .reduce(f64::max)?;
+    let frame = rules
+        .iter()
+        .filter(|rule| rule.end_x - rule.x >= FRAME_RATIO * longest)
+        .collect::<Vec<_>>();
+    let x = frame.iter().map(|rule| rule.x).reduce(f64::min)?;
+    let end_x = frame.iter().map(|rule| rule.end_x).reduce(f64::max)?;
+    let top = frame.iter().map(|rule| rule.y).reduce(f64::max)?;
+    if let Some(rule) = closing_rule(table, rules) {
+        return Some(([x, rule.y, end_x, top], true));
+    }
+    // the page bottom when no row is below the top rule
+    let bottom = table
+        .iter()
+        .filter_map(|row| row.first())
+        .map(|cell| cell.y)
+        .filter(|y| *y < top)
+        .reduce(f64::min)
+        .unwrap_or(0f64);
+    Some(([x, bottom - OVERFLOW_TOLERANCE, end_x, top], false))
+}
+
+/// report the visible runs of a page reaching past the page box less the margins, given as
+/// `[left bottom right top]`, and the runs inside the table frame reaching past its sides or
+/// through the rule closing it
+pub fn check_overflow(
+    page_number: usize,
+    page: &Page,
+    table: &[Vec<Cell>],
+    margins: [f64; 4],
+    report: &Report,
+) {
+    let area = [
+        margins[0],
+        margins[1],
+        page.geometry.width() - margins[2],
+        page.geometry.height() - margins[3],
+    ];
+    let frame = table_frame(table, &page.rules());
+    for run in page.runs.iter().filter(|run| run.visible) {
+        let bounds = run_bounds(run);
+        let text = run.text.trim();
+        let outside = [
+            bounds[0] < area[0] - OVERFLOW_TOLERANCE,
+            bounds[1] < area[1] - OVERFLOW_TOLERANCE,
+            bounds[2] > area[2] + OVERFLOW_TOLERANCE,
+            bounds[3] > area[3] + OVERFLOW_TOLERANCE,
+        ];
+        if let Some(side) = outside.iter().position(|outside| *outside) {
+            let side = ["left", "bottom", "right", "top"][side];
+            report.append_finding(
+                page_number,
+                Kind::Overflow,
+                &format!("\"{}\" runs past the {} margin", text, side),
+            );
+            continue;
+        }
+        let frame = match frame {
+            Some((frame, _)) if run.y > frame[1] && run.y < frame[3] => frame,
+            Some((frame, true))
+                if run.y < frame[1] && bounds[3] > frame[1] + OVERFLOW_TOLERANCE =>
+            {
+                report.append_finding(
+                    page_number,
+                    Kind::Overflow,
+                    &format!("\"{}\" runs through the bottom rule of the table", text),
+                );
+                continue;
+            }
+            _ => continue,
+        };
+        if bounds[0] < frame[0] - OVERFLOW_TOLERANCE || bounds[2] > frame[2] + OVERFLOW_TOLERANCE {
+            report.append_finding(
+                page_number,
+                Kind::Overflow,
+                &format!("\"{}\" runs past the table frame", text),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod overflow_test {
+    use super::*;
+    use crate::pdf::reader::{test_util::run, Segment};
+
+    fn rule(y: f64) -> Segment {
+        Segment {
+            start: (72f64, y),
+            end: (540f64, y),
+            width: 0.5,
+            filled: false,
+        }
+    }
+
+    #[test]
+    fn check_overflow_test() {
+        let mut page = Page {
+            segments: vec![rule(720f64), rule(700f64), rule(600f64)],
+            runs: vec![
+                run("Listing 16.2.1", 72f64, 740f64),
+                run("Subject", 72f64, 708f64),
+                run("Term", 480f64, 708f64),
+                run("01001", 72f64, 680f64),
+                // a wide column past the frame and past the right edge of the page
+                run("a long verbatim term", 480f64, 680f64),
+                run("a longer verbatim term running off", 520f64, 660f64),
+                // the last row drawn through the closing rule
+                run("01009", 72f64, 597f64),
+                run("Note: footnote", 72f64, 580f64),
+                TextRun {
+                    visible: false,
+                    ..run("hidden", 600f64, 500f64)
+                },
+            ],
+            ..Page::default()
+        };
+        let table = page.table(&[]);
+        let report = Report::new("t.pdf");
+        check_overflow(1, &page, &table, [0f64; 4], &report);
+        let messages = report
+            .findings()
+            .iter()
+            .map(|f| f.message.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "\"a long verbatim term\" runs past the table frame",
+                "\"a longer verbatim term running off\" runs past the right margin",
+                "\"01009\" runs through the bottom rule of the table",
+            ]
+        );
+
+        // the margins keep the sponsor line off the top of the page
+        let report = Report::new("t.pdf");
+        page.runs = vec![run("AKESO Inc.", 72f64, 760f64)];
+        check_overflow(1, &page, &table, [72f64, 72f64, 72f64, 36f64], &report);
+        assert_eq!(report.findings().len(), 1);
+        assert_eq!(report.findings()[0].kind, Kind::Overflow);
+
+        // without closing rule the underline of the header is no bottom rule and the frame
+        // reaches down to the last row
+        let page = Page {
+            segments: vec![rule(720f64), rule(700f64)],
+            runs: vec![
+                run("Subject", 72f64, 708f64),
+                run("Term", 480f64, 708f64),
+                run("01001", 72f64, 697f64),
+                run("01002", 72f64, 680f64),
+                run("a long verbatim term", 480f64, 660f64),
+            ],
+            ..Page::default()
+        };
+        let table = page.table(&[]);
+        let report = Report::new("t.pdf");
+        check_overflow(1, &page, &table, [0f64; 4], &report);
+        let messages = report
+            .findings()
+            .iter()
+            .map(|f| f.message.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec!["\"a long verbatim term\" runs past the table frame"]
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use std::cmp::Ordering;
+
+use regex::Regex;
+
+use crate::report::{Kind, Report};
+
+use super::{blocks::Blocks, group};
+
+/// compare key values as numbers when both are numbers, as text otherwise
+fn compare_keys(a: &[String], b: &[String]) -> Ordering {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| match (a.parse::<f64>(), b.parse::<f64>()) {
+            (Ok(a), Ok(b)) => a.total_cmp(&b),
+            _ => a.cmp(b),
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// check that the sort columns of the body rows never decrease over the whole output, a blank
+/// or continued column repeats the value above it
+pub fn check_sort_order(
+    pages: &[Blocks],
+    sort_columns: &[usize],
+    markers: &[Regex],
+    report: &Report,
+) {
+    let mut previous: Option<(usize, Vec<String>)> = None;
+    for (i, blocks) in pages.iter().enumerate() {
+        for row in &blocks.body {
+            let key = match group::row_key(row, sort_columns) {
+                Some(key) => key,
+                None => continue,
+            };
+            let key = key
+                .into_iter()
+                .enumerate()
+                .map(|(column, value)| {
+                    let continued =
+                        value.is_empty() || markers.iter().any(|marker| marker.is_match(&value));
+                    match &previous {
+                        Some((_, above)) if continued => above[column].clone(),
+                        _ => value,
+                    }
+                })
+                .collect::<Vec<_>>();
+            if let Some((page, above)) = &previous {
+                if compare_keys(&key, above).is_lt() {
+                    report.append_finding(
+                        i + 1,
+                        Kind::SortOrder,
+                        &format!(
+                            "\"{}\" sorts before \"{}\" on page {}",
+                            key.join(" "),
+                            above.join(" "),
+                            page
+                        ),
+                    );
+                }
+            }
+            previous = Some((i + 1, key));
+        }
+    }
+}
+
+#[cfg(test)]
+mod sort_test {
+    use super::*;
+    use crate::probe::test_util::{page, pages_with};
+
+    #[test]
+    fn check_sort_order_test() {
+        let markers = [Regex::new(r"(?i)\(cont(inued|\.)?\)").unwrap()];
+        let header = ["Arm", "Subject", "Term"];
+        let pages = vec![
+            page(&header, &[&["A", "9", "Nausea"], &["", "", "Rash"]]),
+            page(&header, &[&["(cont.)", "10", "Fever"]]),
+            // a part merged out of order
+            page(&header, &[&["A", "3", "Cough"]]),
+            page(&header, &[&["B", "1", "Cough"]]),
+        ];
+        let report = Report::new("t.pdf");
+        check_sort_order(&pages, &[0, 1], &markers, &report);
+        let findings = report.findings();
+        assert_eq!(findings.len(), 1);
+        assert_eq!((findings[0].page, findings[0].kind), (3, Kind::SortOrder));
+        assert!(findings[0].message.contains("\"A 10\" on page 2"));
+
+        let report = Report::new("t.pdf");
+        check_sort_order(&pages, &[1], &markers, &report);
+        assert_eq!(pages_with(&report, Kind::SortOrder), vec![3, 4]);
+    }
+}
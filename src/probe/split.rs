@@ -0,0 +1,84 @@
+use crate::{
+    pdf::reader::Cell,
+    report::{Kind, Report},
+};
+
+use super::blocks::{self, Blocks};
+
+/// the first body line of a page when it continues a row broken off the previous page, the table
+/// of the previous page runs on without closing rule and the line leaves the key column blank
+/// while text goes on in later columns
+pub fn split_row<'a>(previous: &Blocks, blocks: &'a Blocks) -> Option<&'a Vec<Cell>> {
+    if previous.body.is_empty() || previous.closed {
+        return None;
+    }
+    let row = blocks.body.first()?;
+    let continued =
+        !row.iter().any(|cell| cell.column == 0) && row.iter().any(|cell| cell.column > 0);
+    continued.then_some(row)
+}
+
+/// report the pages whose body starts with the rest of a row broken off the previous page
+pub fn check_split_rows(pages: &[Blocks], report: &Report) {
+    for (i, blocks) in pages.iter().enumerate().skip(1) {
+        if let Some(row) = split_row(&pages[i - 1], blocks) {
+            report.append_finding(
+                i + 1,
+                Kind::SplitRow,
+                &format!(
+                    "\"{}\" continues a row of page {}",
+                    blocks::row_text(row),
+                    i
+                ),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod split_test {
+    use super::*;
+    use crate::probe::test_util::{page, pages_with};
+
+    fn open(blocks: Blocks) -> Blocks {
+        Blocks {
+            closed: false,
+            ..blocks
+        }
+    }
+
+    fn split_pages(pages: &[Blocks]) -> Vec<usize> {
+        let report = Report::new("t.pdf");
+        check_split_rows(pages, &report);
+        pages_with(&report, Kind::SplitRow)
+    }
+
+    #[test]
+    fn check_split_rows_test() {
+        let header = ["Subject", "Term", "Onset", "Grade"];
+        let full: &[&str] = &["01001", "Nausea", "2023-01-02", "1"];
+        let pages = vec![
+            open(page(&header, &[full])),
+            // the wrapped verbatim term went on over the break
+            open(page(&header, &[&["", "abdominal"], full])),
+            // the rest of a row filling every column but the key
+            page(&header, &[&["", "pain", "2023-01-05", "2"]]),
+            // the table closed on the previous page, a blank key starts no split row
+            page(&header, &[&["", "Rash", "", "2"]]),
+        ];
+        let report = Report::new("t.pdf");
+        check_split_rows(&pages, &report);
+        assert_eq!(pages_with(&report, Kind::SplitRow), vec![2, 3]);
+        assert!(report.findings()[0].message.contains("page 1"));
+    }
+
+    #[test]
+    fn two_columns_test() {
+        let header = ["Parameter", "Value"];
+        let first = open(page(&header, &[&["Comment", "long"]]));
+        let pages = [first.clone(), page(&header, &[&["", "text"]])];
+        assert_eq!(split_pages(&pages), vec![2]);
+        let pages = [first, page(&header, &[&["Dose", "10"]])];
+        assert!(split_pages(&pages).is_empty());
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{
+    pdf::reader::Cell,
+    report::{Kind, Report},
+};
+
+use super::blocks::Blocks;
+
+/// a cell in a column 100 points wide
+pub fn cell(text: &str, column: usize, y: f64, header: bool) -> Cell {
+    Cell {
+        text: text.into(),
+        x: 72f64 + column as f64 * 100f64,
+        end_x: 122f64 + column as f64 * 100f64,
+        y,
+        column,
+        span: 1,
+        header,
+    }
+}
+
+/// a row with a cell for each text in consecutive columns, empty texts are blank columns
+pub fn row(texts: &[&str], header: bool) -> Vec<Cell> {
+    texts
+        .iter()
+        .enumerate()
+        .filter(|(_, text)| !text.is_empty())
+        .map(|(column, text)| cell(text, column, 700f64, header))
+        .collect()
+}
+
+/// a page under a one row header whose table is closed by a rule
+pub fn page(header: &[&str], body: &[&[&str]]) -> Blocks {
+    Blocks {
+        header: vec![row(header, true)],
+        body: body.iter().map(|texts| row(texts, false)).collect(),
+        closed: true,
+        ..Blocks::default()
+    }
+}
+
+/// the pages holding a finding of the kind
+pub fn pages_with(report: &Report, kind: Kind) -> Vec<usize> {
+    report
+        .findings()
+        .iter()
+        .filter(|f| f.kind == kind)
+        .map(|f| f.page)
+        .collect()
+}
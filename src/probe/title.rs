@@ -0,0 +1,102 @@
+use regex::Regex;
+
+use crate::report::{Kind, Report};
+
+use super::blocks::{self, Blocks};
+
+/// the rows of the title block with masks such as page labels and continuation markers taken out,
+/// None for a page without column header where the title block cannot be told from the rest
+pub fn title_block(blocks: &Blocks, masks: &[Regex]) -> Option<Vec<String>> {
+    if blocks.header.is_empty() {
+        return None;
+    }
+    Some(blocks::masked_rows(&blocks.title, masks))
+}
+
+/// compare the title block of every page with the first one found in the output
+pub fn check_titles(titles: &[Option<Vec<String>>], report: &Report) {
+    let mut pages = titles
+        .iter()
+        .enumerate()
+        .filter_map(|(i, title)| title.as_ref().map(|title| (i + 1, title)));
+    let (first_page, first) = match pages.next() {
+        Some(first) => first,
+        None => return,
+    };
+    for (page, title) in pages {
+        if title == first {
+            continue;
+        }
+        let message = match title
+            .iter()
+            .zip(first)
+            .find(|(row, expected)| row != expected)
+        {
+            Some((row, expected)) => format!(
+                "title row \"{}\" differs from \"{}\" on page {}",
+                row, expected, first_page
+            ),
+            None => format!(
+                "title has {} rows, page {} has {}",
+                title.len(),
+                first_page,
+                first.len()
+            ),
+        };
+        report.append_finding(page, Kind::Title, &message);
+    }
+}
+
+#[cfg(test)]
+mod title_test {
+    use super::*;
+    use crate::probe::test_util::{page, pages_with, row};
+
+    #[test]
+    fn title_block_test() {
+        let labels = [
+            Regex::new(r"(?i)\bpage\s+(\d+)\s+of\s+(\d+)").unwrap(),
+            Regex::new(r"(?i)\(cont(inued|\.)?\)").unwrap(),
+        ];
+        let blocks = Blocks {
+            title: vec![
+                row(&["AKESO Inc.", "Page 2 of 9"], false),
+                row(&["Page 2 of 9"], false),
+                row(&["Table 14.1.1  Demographics (Continued)"], false),
+            ],
+            ..page(&["Subject", "Age"], &[])
+        };
+        assert_eq!(
+            title_block(&blocks, &labels),
+            Some(vec![
+                "AKESO Inc.".to_string(),
+                "Table 14.1.1 Demographics".to_string()
+            ])
+        );
+        let figure = Blocks {
+            header: vec![],
+            ..blocks
+        };
+        assert_eq!(title_block(&figure, &labels), None);
+    }
+
+    #[test]
+    fn check_titles_test() {
+        let title = |rows: &[&str]| Some(rows.iter().map(|r| r.to_string()).collect::<Vec<_>>());
+        let report = Report::new("t.pdf");
+        check_titles(
+            &[
+                None,
+                title(&["AKESO Inc.", "Table 14.1.1"]),
+                title(&["AKESO Inc.", "Table 14.1.1"]),
+                title(&["AKESO Inc.", "Table 14.1.2"]),
+                title(&["AKESO Inc."]),
+            ],
+            &report,
+        );
+        assert_eq!(pages_with(&report, Kind::Title), vec![4, 5]);
+        let findings = report.findings();
+        assert!(findings[0].message.contains("Table 14.1.2"));
+        assert!(findings[0].message.contains("page 2"));
+    }
+}
@@ -2,10 +2,56 @@ use std::{cell::RefCell, ops::Deref};
 
 use serde::{Deserialize, Serialize};
 
+/// The kind of defect a finding points at
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Kind {
+    /// nothing below the title and column header, or below the title on a page without figure
+    EmptyBody,
+    /// page number of the page label out of sequence or missing
+    PageNumber,
+    /// page count of the page label other than the pages of the output
+    PageCount,
+    /// title block other than on the first page of the output
+    Title,
+    /// column header rows missing or other than on the first page of the output
+    ColumnHeader,
+    /// footnotes pushed onto a page of their own
+    FootnotePage,
+    /// footnotes missing where the study convention puts them
+    FootnoteMissing,
+    /// footnote text other than on the first page with footnotes
+    FootnoteText,
+    /// page holding far fewer body rows than the other pages of the output
+    Underfull,
+    /// page holding no more than a row or two left over after a break
+    OrphanRows,
+    /// row broken over a page break, its rest opening the next page
+    SplitRow,
+    /// continuation page starting inside a group without repeating its key
+    GroupKey,
+    /// listing rows out of the order of their sort columns
+    SortOrder,
+    /// text reaching past the page margins or the table frame
+    Overflow,
+}
+
+/// A defect found on a page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Finding {
+    pub page: usize,
+    pub kind: Kind,
+    pub message: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Report {
     file: String,
     void: RefCell<Vec<usize>>,
+    #[serde(default)]
+    findings: RefCell<Vec<Finding>>,
+    /// body rows on each page
+    #[serde(default)]
+    body_rows: RefCell<Vec<usize>>,
 }
 
 impl Report {
@@ -13,6 +59,8 @@ impl Report {
         Report {
             file: file.into(),
             void: RefCell::new(vec![]),
+            findings: RefCell::new(vec![]),
+            body_rows: RefCell::new(vec![]),
         }
     }
     pub fn file(&self) -> String {
@@ -25,6 +73,24 @@ impl Report {
     pub fn void(&self) -> Vec<usize> {
         self.void.borrow().deref().to_vec()
     }
+    pub fn append_finding(&self, page: usize, kind: Kind, message: &str) -> &Self {
+        self.findings.borrow_mut().push(Finding {
+            page,
+            kind,
+            message: message.into(),
+        });
+        self
+    }
+    pub fn findings(&self) -> Vec<Finding> {
+        self.findings.borrow().deref().to_vec()
+    }
+    pub fn append_body_rows(&self, rows: usize) -> &Self {
+        self.body_rows.borrow_mut().push(rows);
+        self
+    }
+    pub fn body_rows(&self) -> Vec<usize> {
+        self.body_rows.borrow().deref().to_vec()
+    }
 }
 
 #[cfg(test)]
@@ -37,5 +103,8 @@ mod report_test {
         r.append_void(1).append_void(2).append_void(3);
         assert_eq!(r.void(), vec![1, 2, 3]);
         assert_eq!(r.file(), String::from("test.rtf"));
+        r.append_finding(2, Kind::EmptyBody, "no body rows");
+        assert_eq!(r.findings()[0].page, 2);
+        assert_eq!(r.findings()[0].kind, Kind::EmptyBody);
     }
 }